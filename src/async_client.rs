@@ -0,0 +1,226 @@
+//! Async variant of the spiders, for use inside a tokio runtime
+
+use crate::client::{Page, Pipeline};
+use crate::error::Error;
+use crate::retry;
+use crate::{Period, Spider, SpiderClient};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Async counterpart of [`SpiderClient`]
+#[derive(Debug, Clone)]
+pub struct AsyncSpiderClient {
+    client: reqwest::Client,
+    pipeline: Arc<Pipeline>,
+}
+
+impl AsyncSpiderClient {
+    /// Create a client with the default configuration
+    pub fn new() -> Result<Self, reqwest::Error> {
+        SpiderClient::builder().build_async()
+    }
+
+    pub(crate) fn from_parts(client: reqwest::Client, pipeline: Arc<Pipeline>) -> Self {
+        AsyncSpiderClient { client, pipeline }
+    }
+
+    /// Root url all statement pages are fetched from
+    pub fn base_url(&self) -> &str {
+        &self.pipeline.base_url
+    }
+
+    /// Download the `period` page of `S` for `symbol`
+    pub async fn fetch<S: Spider>(&self, symbol: &str, period: Period) -> Result<String, Error> {
+        let url = S::url(self.base_url(), symbol, period);
+        println!("---> Fetching {} ({period}) for: {symbol}", S::NAME);
+        println!("---> {url}");
+        self.get(&url).await
+    }
+
+    /// Download the body of `url`. In replay mode it is served from
+    /// the recorded fixtures, otherwise from the cache while fresh.
+    pub async fn get(&self, url: &str) -> Result<String, Error> {
+        let (pipeline, key) = (self.pipeline.clone(), url.to_string());
+        if let Some(res) = blocking(move || pipeline.lookup(&key)).await {
+            return res;
+        }
+
+        let downloaded = self.download(url).await;
+        let (pipeline, key) = (self.pipeline.clone(), url.to_string());
+        blocking(move || pipeline.finish(&key, downloaded)).await
+    }
+
+    /// Download `url`, retrying failures according to the retry policy
+    async fn download(&self, url: &str) -> Result<String, Error> {
+        let retry = &self.pipeline.retry;
+        let mut attempt = 0;
+
+        loop {
+            match self.try_download(url).await {
+                Err(err) if retry.should_retry(attempt, &err) => {
+                    tokio::time::sleep(retry.delay(attempt, &err)).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    async fn try_download(&self, url: &str) -> Result<String, Error> {
+        let mut page = self.send(url).await?;
+
+        if let Some((action, fields)) = self.pipeline.consent_form(&page) {
+            println!("---> Answering the cookie consent form");
+            tokio::time::sleep(self.pipeline.rate_limiter.reserve(action.as_str())).await;
+            let response = self.client.post(action).form(&fields).send().await?;
+            retry::check_status(url, response.url(), response.status(), response.headers())?;
+            page = self.send(url).await?;
+        }
+
+        self.pipeline.check(url, &page)?;
+        Ok(page.body)
+    }
+
+    /// Request `url`
+    async fn send(&self, url: &str) -> Result<Page, Error> {
+        tokio::time::sleep(self.pipeline.rate_limiter.reserve(url)).await;
+        let response = self.client.get(url).send().await?;
+        let final_url = response.url().clone();
+        retry::check_status(url, &final_url, response.status(), response.headers())?;
+        let content_type = Page::content_type(response.headers());
+
+        Ok(Page {
+            url: final_url,
+            content_type,
+            body: response.text().await?,
+        })
+    }
+
+    /// Download the `period` pages of `S` for every symbol, running at most
+    /// `concurrency` requests at a time. Results are returned in the
+    /// same order as `symbols`.
+    pub async fn fetch_all<S: Spider + 'static>(
+        &self,
+        symbols: &[&str],
+        period: Period,
+        concurrency: usize,
+    ) -> Vec<Result<String, Error>> {
+        let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+
+        for (index, symbol) in symbols.iter().enumerate() {
+            let client = self.clone();
+            let permits = permits.clone();
+            let symbol = symbol.to_string();
+
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                (index, client.fetch::<S>(&symbol, period).await)
+            });
+        }
+
+        let mut res: Vec<Option<Result<String, Error>>> = symbols.iter().map(|_| None).collect();
+
+        while let Some(task) = tasks.join_next().await {
+            match task {
+                Ok((index, html)) => res[index] = Some(html),
+                Err(err) => std::panic::resume_unwind(err.into_panic()),
+            }
+        }
+
+        res.into_iter().flatten().collect()
+    }
+}
+
+/// Run `f` on tokio's blocking thread pool, for the file access of the
+/// cache and the fixtures
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(res) => res,
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
+
+/// Async variant of [`Spider::fetch`], available for every spider
+pub trait AsyncSpider: Spider {
+    /// Download the `period` page for `symbol` using a default
+    /// [`AsyncSpiderClient`]
+    fn fetch_async(
+        symbol: &str,
+        period: Period,
+    ) -> impl Future<Output = Result<String, Error>> + Send;
+}
+
+impl<S: Spider> AsyncSpider for S {
+    fn fetch_async(
+        symbol: &str,
+        period: Period,
+    ) -> impl Future<Output = Result<String, Error>> + Send {
+        let symbol = symbol.to_string();
+        async move { AsyncSpiderClient::new()?.fetch::<S>(&symbol, period).await }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::Cache;
+    use crate::mock::{MockResponse, MockServer};
+
+    struct Page;
+    impl Spider for Page {
+        const NAME: &'static str = "Test Page";
+
+        fn url(root: &str, symbol: &str, period: Period) -> String {
+            format!("{root}/quote/{symbol}/page{}", period.query())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_fetch_all() {
+        let symbols = ["AAA", "BBB", "CCC", "DDD"];
+        let server = MockServer::start(
+            symbols
+                .iter()
+                .map(|_| MockResponse::ok("<html></html>"))
+                .collect(),
+        );
+
+        let dir = std::env::temp_dir().join(format!("finspider-async-{}", std::process::id()));
+        let client = SpiderClient::builder()
+            .base_url(&server.url)
+            .cache(Cache::new(&dir))
+            .build_async()
+            .unwrap();
+
+        let res = client
+            .fetch_all::<Page>(&symbols, Period::Quarterly, 2)
+            .await;
+        let mut paths: Vec<String> = server
+            .requests()
+            .iter()
+            .map(|head| head[0].clone())
+            .collect();
+        paths.sort();
+
+        assert_eq!(res.len(), 4);
+        assert!(res
+            .iter()
+            .all(|html| html.as_ref().unwrap() == "<html></html>"));
+        assert_eq!(paths[0], "GET /quote/AAA/page?frequency=quarterly HTTP/1.1");
+        assert_eq!(paths[3], "GET /quote/DDD/page?frequency=quarterly HTTP/1.1");
+
+        // The server is gone, the page is served from the cache
+        assert_eq!(
+            client
+                .fetch::<Page>("BBB", Period::Quarterly)
+                .await
+                .unwrap(),
+            "<html></html>"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,48 +1,17 @@
 use super::BalanceSheet;
+use crate::database;
 use crate::error::Error;
+use crate::store::{OnConflict, Saved};
+use chrono::{DateTime, NaiveDate, Utc};
 use postgres::Client;
 
 /// Database management for balance sheets
 pub struct BalanceSheetsDB;
 impl BalanceSheetsDB {
-    /// Initialize postgres cache
+    /// Initialize postgres cache, migrating tables created by older
+    /// versions
     pub fn init(client: &mut Client, table_name: &str, db_owner: &str) -> Result<(), Error> {
-        let sql = format!(
-            "
-CREATE TABLE IF NOT EXISTS {table_name} (
-	id serial NOT NULL,
-        symbol varchar(80) NOT NULL,
-        term date NOT NULL,
-        total_assets varchar(100),
-        total_liabilities_net_minority_interest varchar(100),
-        total_equity_gross_minority_interest varchar(100),
-        total_capitalization varchar(100),
-        preferred_stock_equity varchar(100),
-        common_stock_equity varchar(100),
-        net_tangible_assets varchar(100),
-        invested_capital varchar(100),
-        tangible_book_value varchar(100),
-        total_debt varchar(100),
-        net_debt varchar(100),
-        share_issued varchar(100),
-        ordinary_shares_number varchar(100),
-        preferred_shares_number varchar(100),
-        treasury_shares_number varchar(100),
-        working_capital varchar(100),
-        capital_lease_obligations varchar(100),
-        filed date NOT NULL,
-        hash text UNIQUE NOT NULL,
-        version smallint NOT NULL
-);
--- ddl-end --
-ALTER TABLE {table_name} OWNER TO {db_owner};
--- ddl-end --"
-        );
-        client
-            .batch_execute(&sql)
-            .map_err(|_| Error::TableCreation)?;
-
-        Ok(())
+        database::init::<BalanceSheet>(client, table_name, db_owner)
     }
 
     /// Save balance sheet in database
@@ -51,65 +20,18 @@ ALTER TABLE {table_name} OWNER TO {db_owner};
         table_name: &str,
         balance_sheet: BalanceSheet,
     ) -> Result<(), Error> {
-        let sql = format!(
-            "INSERT INTO {table_name} (
-        symbol,
-        term,
-        total_assets,
-        total_liabilities_net_minority_interest,
-        total_equity_gross_minority_interest,
-        total_capitalization,
-        preferred_stock_equity,
-        common_stock_equity,
-        net_tangible_assets,
-        invested_capital,
-        tangible_book_value,
-        total_debt,
-        net_debt,
-        share_issued,
-        ordinary_shares_number,
-        preferred_shares_number,
-        treasury_shares_number,
-        working_capital,
-        capital_lease_obligations,
-        filed,
-        hash,
-        version
-)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
-                         $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22);"
-        );
+        database::save(client, table_name, &balance_sheet)
+    }
 
-        client
-            .execute(
-                &sql,
-                &[
-                    &balance_sheet.symbol,
-                    &balance_sheet.term,
-                    &balance_sheet.total_assets,
-                    &balance_sheet.total_liabilities_net_minority_interest,
-                    &balance_sheet.total_equity_gross_minority_interest,
-                    &balance_sheet.total_capitalization,
-                    &balance_sheet.preferred_stock_equity,
-                    &balance_sheet.common_stock_equity,
-                    &balance_sheet.net_tangible_assets,
-                    &balance_sheet.invested_capital,
-                    &balance_sheet.tangible_book_value,
-                    &balance_sheet.total_debt,
-                    &balance_sheet.net_debt,
-                    &balance_sheet.share_issued,
-                    &balance_sheet.ordinary_shares_number,
-                    &balance_sheet.preferred_shares_number,
-                    &balance_sheet.treasury_shares_number,
-                    &balance_sheet.working_capital,
-                    &balance_sheet.capital_lease_obligations,
-                    &balance_sheet.filed,
-                    &balance_sheet.hash(),
-                    &balance_sheet.version,
-                ],
-            )
-            .map_err(|_| Error::SQL)?;
-        Ok(())
+    /// Save balance sheet in database, doing `on_conflict` when one with the
+    /// same hash is already stored
+    pub fn upsert(
+        client: &mut Client,
+        table_name: &str,
+        balance_sheet: BalanceSheet,
+        on_conflict: OnConflict,
+    ) -> Result<Saved, Error> {
+        database::upsert(client, table_name, &balance_sheet, on_conflict)
     }
 
     pub fn read(
@@ -117,73 +39,11 @@ ALTER TABLE {table_name} OWNER TO {db_owner};
         table_name: &str,
         hash: &str,
     ) -> Result<Option<BalanceSheet>, Error> {
-        let sql = format!("SELECT * FROM {table_name} WHERE hash = $1");
-        let row = client.query(&sql, &[&hash]).map_err(|_| Error::SQL)?;
-
-        if row.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(BalanceSheet {
-                symbol: row[0].get("symbol"),
-                term: row[0].get("term"),
-                total_assets: row[0].get("total_assets"),
-                total_liabilities_net_minority_interest: row[0]
-                    .get("total_liabilities_net_minority_interest"),
-                total_equity_gross_minority_interest: row[0]
-                    .get("total_equity_gross_minority_interest"),
-                total_capitalization: row[0].get("total_capitalization"),
-                preferred_stock_equity: row[0].get("preferred_stock_equity"),
-                common_stock_equity: row[0].get("common_stock_equity"),
-                net_tangible_assets: row[0].get("net_tangible_assets"),
-                invested_capital: row[0].get("invested_capital"),
-                tangible_book_value: row[0].get("tangible_book_value"),
-                total_debt: row[0].get("total_debt"),
-                net_debt: row[0].get("net_debt"),
-                share_issued: row[0].get("share_issued"),
-                ordinary_shares_number: row[0].get("ordinary_shares_number"),
-                preferred_shares_number: row[0].get("preferred_shares_number"),
-                treasury_shares_number: row[0].get("treasury_shares_number"),
-                working_capital: row[0].get("working_capital"),
-                capital_lease_obligations: row[0].get("capital_lease_obligations"),
-                version: row[0].get("version"),
-                filed: row[0].get("filed"),
-            }))
-        }
+        database::read(client, table_name, hash)
     }
 
     pub fn read_all(client: &mut Client, table_name: &str) -> Result<Vec<BalanceSheet>, Error> {
-        let sql = format!("SELECT * FROM {table_name}");
-        let row = client.query(&sql, &[]).map_err(|_| Error::SQL)?;
-        let mut balance_sheet: Vec<BalanceSheet> = vec![];
-
-        for r in &row {
-            balance_sheet.push(BalanceSheet {
-                symbol: r.get("symbol"),
-                term: r.get("term"),
-                total_assets: r.get("total_assets"),
-                total_liabilities_net_minority_interest: r
-                    .get("total_liabilities_net_minority_interest"),
-                total_equity_gross_minority_interest: r.get("total_equity_gross_minority_interest"),
-                total_capitalization: r.get("total_capitalization"),
-                preferred_stock_equity: r.get("preferred_stock_equity"),
-                common_stock_equity: r.get("common_stock_equity"),
-                net_tangible_assets: r.get("net_tangible_assets"),
-                invested_capital: r.get("invested_capital"),
-                tangible_book_value: r.get("tangible_book_value"),
-                total_debt: r.get("total_debt"),
-                net_debt: r.get("net_debt"),
-                share_issued: r.get("share_issued"),
-                ordinary_shares_number: r.get("ordinary_shares_number"),
-                preferred_shares_number: r.get("preferred_shares_number"),
-                treasury_shares_number: r.get("treasury_shares_number"),
-                working_capital: r.get("working_capital"),
-                capital_lease_obligations: r.get("capital_lease_obligations"),
-                filed: r.get("filed"),
-                version: r.get("version"),
-            })
-        }
-
-        Ok(balance_sheet)
+        database::read_all(client, table_name)
     }
 
     pub fn read_all_by_symbol(
@@ -191,48 +51,61 @@ ALTER TABLE {table_name} OWNER TO {db_owner};
         table_name: &str,
         symbol: &str,
     ) -> Result<Vec<BalanceSheet>, Error> {
-        let sql = format!("SELECT * FROM {table_name} WHERE symbol = $1");
-        let row = client.query(&sql, &[&symbol]).map_err(|_| Error::SQL)?;
-        let mut balance_sheet: Vec<BalanceSheet> = vec![];
+        database::read_all_by_symbol(client, table_name, symbol)
+    }
 
-        for r in &row {
-            balance_sheet.push(BalanceSheet {
-                symbol: r.get("symbol"),
-                term: r.get("term"),
-                total_assets: r.get("total_assets"),
-                total_liabilities_net_minority_interest: r
-                    .get("total_liabilities_net_minority_interest"),
-                total_equity_gross_minority_interest: r.get("total_equity_gross_minority_interest"),
-                total_capitalization: r.get("total_capitalization"),
-                preferred_stock_equity: r.get("preferred_stock_equity"),
-                common_stock_equity: r.get("common_stock_equity"),
-                net_tangible_assets: r.get("net_tangible_assets"),
-                invested_capital: r.get("invested_capital"),
-                tangible_book_value: r.get("tangible_book_value"),
-                total_debt: r.get("total_debt"),
-                net_debt: r.get("net_debt"),
-                share_issued: r.get("share_issued"),
-                ordinary_shares_number: r.get("ordinary_shares_number"),
-                preferred_shares_number: r.get("preferred_shares_number"),
-                treasury_shares_number: r.get("treasury_shares_number"),
-                working_capital: r.get("working_capital"),
-                capital_lease_obligations: r.get("capital_lease_obligations"),
-                filed: r.get("filed"),
-                version: r.get("version"),
-            })
-        }
+    /// Read the balance sheets of `symbol` with a term from `from` to `to`,
+    /// oldest first
+    pub fn read_range(
+        client: &mut Client,
+        table_name: &str,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<BalanceSheet>, Error> {
+        database::read_range(client, table_name, symbol, from, to)
+    }
+
+    /// Delete the statement identified by `hash`, returning whether it
+    /// was stored
+    pub fn delete(client: &mut Client, table_name: &str, hash: &str) -> Result<bool, Error> {
+        database::delete(client, table_name, hash)
+    }
+
+    /// Save balance sheet in database as the current version of its term from
+    /// `at`, moving the version it restates to the history
+    pub fn revise(
+        client: &mut Client,
+        table_name: &str,
+        balance_sheet: BalanceSheet,
+        at: DateTime<Utc>,
+    ) -> Result<Saved, Error> {
+        database::revise(client, table_name, &balance_sheet, at)
+    }
 
-        Ok(balance_sheet)
+    /// Read the balance sheets of `symbol` with a term from `from` to `to` as
+    /// they were stored at `at`, oldest first
+    pub fn read_as_of(
+        client: &mut Client,
+        table_name: &str,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<BalanceSheet>, Error> {
+        database::read_as_of(client, table_name, symbol, from, to, at)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Decimal, FinancialStatement, Period, Source, TermKind};
     use chrono::Datelike;
     use postgres::{Client, NoTls};
+    use std::collections::BTreeMap;
     use std::env;
-    const TABLE: &'static str = "balance_sheet_test_database";
+    const TABLE: &str = "balance_sheet_test_database";
 
     /// Test the routines for the balance sheets db
     #[test]
@@ -260,24 +133,50 @@ mod tests {
         let balance_sheet = BalanceSheet {
             symbol: "SBKP.JO".to_string(),
             term: date,
-            total_assets: Some("1000.00".to_string()),
-            total_liabilities_net_minority_interest: Some("1000.00".to_string()),
-            total_equity_gross_minority_interest: Some("1000.00".to_string()),
-            total_capitalization: Some("1000.00".to_string()),
-            preferred_stock_equity: Some("1000.00".to_string()),
-            common_stock_equity: Some("1000.00".to_string()),
-            net_tangible_assets: Some("1000.00".to_string()),
-            invested_capital: Some("1000.00".to_string()),
-            tangible_book_value: Some("1000.00".to_string()),
-            total_debt: Some("1000.00".to_string()),
-            net_debt: Some("1000.00".to_string()),
-            share_issued: Some("1000.00".to_string()),
-            ordinary_shares_number: Some("1000.00".to_string()),
-            preferred_shares_number: Some("1000.00".to_string()),
-            treasury_shares_number: Some("1000.00".to_string()),
-            working_capital: Some("1000.00".to_string()),
-            capital_lease_obligations: Some("1000.00".to_string()),
+            period: Period::Quarterly,
+            kind: TermKind::Ttm,
+            source: Source::Yahoo,
+            total_assets: Some(Decimal::new(100000, 2)),
+            total_liabilities_net_minority_interest: Some(Decimal::new(100000, 2)),
+            total_equity_gross_minority_interest: Some(Decimal::new(100000, 2)),
+            total_capitalization: Some(Decimal::new(100000, 2)),
+            preferred_stock_equity: Some(Decimal::new(100000, 2)),
+            common_stock_equity: Some(Decimal::new(100000, 2)),
+            net_tangible_assets: Some(Decimal::new(100000, 2)),
+            invested_capital: Some(Decimal::new(100000, 2)),
+            tangible_book_value: Some(Decimal::new(100000, 2)),
+            total_debt: Some(Decimal::new(100000, 2)),
+            net_debt: Some(Decimal::new(100000, 2)),
+            share_issued: Some(Decimal::new(100000, 2)),
+            ordinary_shares_number: Some(Decimal::new(100000, 2)),
+            preferred_shares_number: Some(Decimal::new(100000, 2)),
+            treasury_shares_number: Some(Decimal::new(100000, 2)),
+            working_capital: Some(Decimal::new(100000, 2)),
+            capital_lease_obligations: Some(Decimal::new(100000, 2)),
+            current_assets: Some(Decimal::new(100000, 2)),
+            cash_cash_equivalents_and_short_term_investments: Some(Decimal::new(100000, 2)),
+            cash_and_cash_equivalents: Some(Decimal::new(100000, 2)),
+            other_short_term_investments: Some(Decimal::new(100000, 2)),
+            receivables: Some(Decimal::new(100000, 2)),
+            accounts_receivable: Some(Decimal::new(100000, 2)),
+            inventory: Some(Decimal::new(100000, 2)),
+            total_non_current_assets: Some(Decimal::new(100000, 2)),
+            net_ppe: Some(Decimal::new(100000, 2)),
+            goodwill_and_other_intangible_assets: Some(Decimal::new(100000, 2)),
+            current_liabilities: Some(Decimal::new(100000, 2)),
+            payables_and_accrued_expenses: Some(Decimal::new(100000, 2)),
+            accounts_payable: Some(Decimal::new(100000, 2)),
+            current_debt_and_capital_lease_obligation: Some(Decimal::new(100000, 2)),
+            current_debt: Some(Decimal::new(100000, 2)),
+            total_non_current_liabilities_net_minority_interest: Some(Decimal::new(100000, 2)),
+            long_term_debt_and_capital_lease_obligation: Some(Decimal::new(100000, 2)),
+            long_term_debt: Some(Decimal::new(100000, 2)),
+            stockholders_equity: Some(Decimal::new(100000, 2)),
+            retained_earnings: Some(Decimal::new(100000, 2)),
+            minority_interest: Some(Decimal::new(100000, 2)),
+            extra: BTreeMap::from([("Unknown Row".to_string(), Some(Decimal::new(100000, 2)))]),
             filed: date,
+            filing: None,
             version: 0,
         };
 
@@ -290,7 +189,7 @@ mod tests {
         BalanceSheetsDB::save(&mut db, TABLE, balance_sheet.clone()).unwrap();
 
         // Retrieved saved Balance Sheet from database, by using its hash
-        let res = BalanceSheetsDB::read(&mut db, TABLE, &hash).unwrap();
+        let res = BalanceSheetsDB::read(&mut db, TABLE, hash).unwrap();
 
         if let Some(res) = res {
             assert_eq!(res, balance_sheet.clone());
@@ -312,7 +211,7 @@ mod tests {
     }
 
     fn drop_database(client: &mut Client) {
-        let sql = format!("DROP TABLE IF EXISTS {TABLE};");
+        let sql = format!("DROP TABLE IF EXISTS {TABLE}, {TABLE}_history;");
 
         client
             .batch_execute(&sql)
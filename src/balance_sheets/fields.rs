@@ -0,0 +1,474 @@
+//! Known line items of the balance sheet
+
+use crate::statement::{FieldMeta, Sign, Unit};
+
+/// Every known balance sheet line item, in declaration order
+pub const FIELDS: &[FieldMeta] = &[
+    FieldMeta {
+        name: "total_assets",
+        title: "Total Assets",
+        key: "TotalAssets",
+        description: "Everything the company owns",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "total_liabilities_net_minority_interest",
+        title: "Total Liabilities Net Minority Interest",
+        key: "TotalLiabilitiesNetMinorityInterest",
+        description: "Everything the company owes, excluding minority interest",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "total_equity_gross_minority_interest",
+        title: "Total Equity Gross Minority Interest",
+        key: "TotalEquityGrossMinorityInterest",
+        description: "Shareholders' equity including minority interest",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "total_capitalization",
+        title: "Total Capitalization",
+        key: "TotalCapitalization",
+        description: "Long term debt plus shareholders' equity",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "preferred_stock_equity",
+        title: "Preferred Stock Equity",
+        key: "PreferredStockEquity",
+        description: "Equity attributable to preferred shareholders",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "common_stock_equity",
+        title: "Common Stock Equity",
+        key: "CommonStockEquity",
+        description: "Equity attributable to common shareholders",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "net_tangible_assets",
+        title: "Net Tangible Assets",
+        key: "NetTangibleAssets",
+        description: "Common equity less goodwill and other intangible assets",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "invested_capital",
+        title: "Invested Capital",
+        key: "InvestedCapital",
+        description: "Common equity plus total debt",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "tangible_book_value",
+        title: "Tangible Book Value",
+        key: "TangibleBookValue",
+        description: "Book value less goodwill and other intangible assets",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "total_debt",
+        title: "Total Debt",
+        key: "TotalDebt",
+        description: "Short and long term debt including lease obligations",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "net_debt",
+        title: "Net Debt",
+        key: "NetDebt",
+        description: "Total debt less cash and cash equivalents",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "share_issued",
+        title: "Share Issued",
+        key: "ShareIssued",
+        description: "Number of shares issued, including treasury shares",
+        unit: Unit::Shares,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "ordinary_shares_number",
+        title: "Ordinary Shares Number",
+        key: "OrdinarySharesNumber",
+        description: "Number of ordinary shares outstanding",
+        unit: Unit::Shares,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "preferred_shares_number",
+        title: "Preferred Shares Number",
+        key: "PreferredSharesNumber",
+        description: "Number of preferred shares outstanding",
+        unit: Unit::Shares,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "treasury_shares_number",
+        title: "Treasury Shares Number",
+        key: "TreasurySharesNumber",
+        description: "Number of shares held by the company itself",
+        unit: Unit::Shares,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "working_capital",
+        title: "Working Capital",
+        key: "WorkingCapital",
+        description: "Current assets less current liabilities",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "capital_lease_obligations",
+        title: "Capital Lease Obligations",
+        key: "CapitalLeaseObligations",
+        description: "Liabilities under capital and finance leases",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "current_assets",
+        title: "Current Assets",
+        key: "CurrentAssets",
+        description: "Assets expected to be turned into cash within a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("total_assets"),
+    },
+    FieldMeta {
+        name: "cash_cash_equivalents_and_short_term_investments",
+        title: "Cash, Cash Equivalents & Short Term Investments",
+        key: "CashCashEquivalentsAndShortTermInvestments",
+        description: "Cash plus investments that can be sold quickly",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("current_assets"),
+    },
+    FieldMeta {
+        name: "cash_and_cash_equivalents",
+        title: "Cash And Cash Equivalents",
+        key: "CashAndCashEquivalents",
+        description: "Cash and deposits that can be withdrawn on demand",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("cash_cash_equivalents_and_short_term_investments"),
+    },
+    FieldMeta {
+        name: "other_short_term_investments",
+        title: "Other Short Term Investments",
+        key: "OtherShortTermInvestments",
+        description: "Investments maturing within a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("cash_cash_equivalents_and_short_term_investments"),
+    },
+    FieldMeta {
+        name: "receivables",
+        title: "Receivables",
+        key: "Receivables",
+        description: "Amounts owed to the company",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("current_assets"),
+    },
+    FieldMeta {
+        name: "accounts_receivable",
+        title: "Accounts receivable",
+        key: "AccountsReceivable",
+        description: "Amounts owed by customers",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("receivables"),
+    },
+    FieldMeta {
+        name: "inventory",
+        title: "Inventory",
+        key: "Inventory",
+        description: "Goods and materials held for sale",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("current_assets"),
+    },
+    FieldMeta {
+        name: "total_non_current_assets",
+        title: "Total non-current assets",
+        key: "TotalNonCurrentAssets",
+        description: "Assets held for longer than a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("total_assets"),
+    },
+    FieldMeta {
+        name: "net_ppe",
+        title: "Net PPE",
+        key: "NetPPE",
+        description: "Property, plant and equipment less depreciation",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("total_non_current_assets"),
+    },
+    FieldMeta {
+        name: "goodwill_and_other_intangible_assets",
+        title: "Goodwill And Other Intangible Assets",
+        key: "GoodwillAndOtherIntangibleAssets",
+        description: "Goodwill, patents, brands and other assets without physical form",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("total_non_current_assets"),
+    },
+    FieldMeta {
+        name: "current_liabilities",
+        title: "Current Liabilities",
+        key: "CurrentLiabilities",
+        description: "Obligations due within a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("total_liabilities_net_minority_interest"),
+    },
+    FieldMeta {
+        name: "payables_and_accrued_expenses",
+        title: "Payables And Accrued Expenses",
+        key: "PayablesAndAccruedExpenses",
+        description: "Amounts owed to suppliers and expenses not yet paid",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("current_liabilities"),
+    },
+    FieldMeta {
+        name: "accounts_payable",
+        title: "Accounts Payable",
+        key: "AccountsPayable",
+        description: "Amounts owed to suppliers",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("payables_and_accrued_expenses"),
+    },
+    FieldMeta {
+        name: "current_debt_and_capital_lease_obligation",
+        title: "Current Debt And Capital Lease Obligation",
+        key: "CurrentDebtAndCapitalLeaseObligation",
+        description: "Debt and lease obligations due within a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("current_liabilities"),
+    },
+    FieldMeta {
+        name: "current_debt",
+        title: "Current Debt",
+        key: "CurrentDebt",
+        description: "Debt due within a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("current_debt_and_capital_lease_obligation"),
+    },
+    FieldMeta {
+        name: "total_non_current_liabilities_net_minority_interest",
+        title: "Total Non Current Liabilities Net Minority Interest",
+        key: "TotalNonCurrentLiabilitiesNetMinorityInterest",
+        description: "Obligations due after more than a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("total_liabilities_net_minority_interest"),
+    },
+    FieldMeta {
+        name: "long_term_debt_and_capital_lease_obligation",
+        title: "Long Term Debt And Capital Lease Obligation",
+        key: "LongTermDebtAndCapitalLeaseObligation",
+        description: "Debt and lease obligations due after more than a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("total_non_current_liabilities_net_minority_interest"),
+    },
+    FieldMeta {
+        name: "long_term_debt",
+        title: "Long Term Debt",
+        key: "LongTermDebt",
+        description: "Debt due after more than a year",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("long_term_debt_and_capital_lease_obligation"),
+    },
+    FieldMeta {
+        name: "stockholders_equity",
+        title: "Stockholders' Equity",
+        key: "StockholdersEquity",
+        description: "Equity attributable to the shareholders of the company",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: Some("total_equity_gross_minority_interest"),
+    },
+    FieldMeta {
+        name: "retained_earnings",
+        title: "Retained Earnings",
+        key: "RetainedEarnings",
+        description: "Accumulated profits not paid out as dividends",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: Some("stockholders_equity"),
+    },
+    FieldMeta {
+        name: "minority_interest",
+        title: "Minority Interest",
+        key: "MinorityInterest",
+        description: "Equity of subsidiaries owned by other shareholders",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: Some("total_equity_gross_minority_interest"),
+    },
+];
+
+/// Investing.com row titles and the fields they are stored in
+pub const INVESTING: &[(&str, &str)] = &[
+    ("Total Current Assets", "current_assets"),
+    (
+        "Cash and Short Term Investments",
+        "cash_cash_equivalents_and_short_term_investments",
+    ),
+    ("Cash & Equivalents", "cash_and_cash_equivalents"),
+    ("Short Term Investments", "other_short_term_investments"),
+    ("Total Receivables, Net", "receivables"),
+    ("Accounts Receivables - Trade, Net", "accounts_receivable"),
+    ("Total Inventory", "inventory"),
+    ("Total Assets", "total_assets"),
+    ("Property/Plant/Equipment, Total - Net", "net_ppe"),
+    ("Total Current Liabilities", "current_liabilities"),
+    ("Accounts Payable", "accounts_payable"),
+    ("Notes Payable/Short Term Debt", "current_debt"),
+    (
+        "Total Liabilities",
+        "total_liabilities_net_minority_interest",
+    ),
+    (
+        "Total Long Term Debt",
+        "long_term_debt_and_capital_lease_obligation",
+    ),
+    ("Long Term Debt", "long_term_debt"),
+    ("Capital Lease Obligations", "capital_lease_obligations"),
+    ("Minority Interest", "minority_interest"),
+    ("Total Equity", "stockholders_equity"),
+    (
+        "Retained Earnings (Accumulated Deficit)",
+        "retained_earnings",
+    ),
+    ("Total Common Shares Outstanding", "ordinary_shares_number"),
+];
+
+/// US-GAAP tags and the fields they are stored in, preferred tags first
+pub const US_GAAP: &[(&str, &str)] = &[
+    ("Assets", "total_assets"),
+    ("Liabilities", "total_liabilities_net_minority_interest"),
+    (
+        "StockholdersEquityIncludingPortionAttributableToNoncontrollingInterest",
+        "total_equity_gross_minority_interest",
+    ),
+    ("PreferredStockValue", "preferred_stock_equity"),
+    ("CommonStockSharesIssued", "share_issued"),
+    ("CommonStockSharesOutstanding", "ordinary_shares_number"),
+    ("PreferredStockSharesOutstanding", "preferred_shares_number"),
+    ("TreasuryStockShares", "treasury_shares_number"),
+    ("FinanceLeaseLiability", "capital_lease_obligations"),
+    ("AssetsCurrent", "current_assets"),
+    (
+        "CashCashEquivalentsAndShortTermInvestments",
+        "cash_cash_equivalents_and_short_term_investments",
+    ),
+    (
+        "CashAndCashEquivalentsAtCarryingValue",
+        "cash_and_cash_equivalents",
+    ),
+    ("ShortTermInvestments", "other_short_term_investments"),
+    (
+        "MarketableSecuritiesCurrent",
+        "other_short_term_investments",
+    ),
+    ("ReceivablesNetCurrent", "receivables"),
+    ("AccountsReceivableNetCurrent", "accounts_receivable"),
+    ("InventoryNet", "inventory"),
+    ("AssetsNoncurrent", "total_non_current_assets"),
+    ("PropertyPlantAndEquipmentNet", "net_ppe"),
+    ("LiabilitiesCurrent", "current_liabilities"),
+    (
+        "AccountsPayableAndAccruedLiabilitiesCurrent",
+        "payables_and_accrued_expenses",
+    ),
+    ("AccountsPayableCurrent", "accounts_payable"),
+    ("DebtCurrent", "current_debt"),
+    ("LongTermDebtCurrent", "current_debt"),
+    (
+        "LiabilitiesNoncurrent",
+        "total_non_current_liabilities_net_minority_interest",
+    ),
+    ("LongTermDebtNoncurrent", "long_term_debt"),
+    ("StockholdersEquity", "stockholders_equity"),
+    ("RetainedEarningsAccumulatedDeficit", "retained_earnings"),
+    ("MinorityInterest", "minority_interest"),
+];
@@ -1,387 +1,372 @@
-use crate::{Spider, USER_AGENT, YAHOO_ROOT};
-use base64::prelude::*;
-use chrono::{Datelike, NaiveDate};
-use scraper::{Html, Selector};
+use crate::error::{Error, ParseError};
+use crate::provider::Provider;
+use crate::statement::{FieldMeta, Filing, StatementType};
+use crate::{yahoo, Decimal, FinancialStatement, Period, Source, Spider, TermKind};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[cfg(feature = "postgres")]
 pub mod database;
+pub mod fields;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 
-pub const BALANCE_SHEETS_SCHEMA_VERSION: i16 = 0;
+/// Same for every statement type, see [`SCHEMA_VERSION`](crate::statement::SCHEMA_VERSION)
+pub const BALANCE_SHEETS_SCHEMA_VERSION: i16 = crate::statement::SCHEMA_VERSION;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BalanceSheet {
     pub symbol: String,
     pub term: NaiveDate,
-    pub total_assets: Option<String>,
-    pub total_liabilities_net_minority_interest: Option<String>,
-    pub total_equity_gross_minority_interest: Option<String>,
-    pub total_capitalization: Option<String>,
-    pub preferred_stock_equity: Option<String>,
-    pub common_stock_equity: Option<String>,
-    pub net_tangible_assets: Option<String>,
-    pub invested_capital: Option<String>,
-    pub tangible_book_value: Option<String>,
-    pub total_debt: Option<String>,
-    pub net_debt: Option<String>,
-    pub share_issued: Option<String>,
-    pub ordinary_shares_number: Option<String>,
-    pub preferred_shares_number: Option<String>,
-    pub treasury_shares_number: Option<String>,
-    pub working_capital: Option<String>,
-    pub capital_lease_obligations: Option<String>,
-    #[cfg(feature = "postgres")]
+    pub period: Period,
+    pub kind: TermKind,
+    pub source: Source,
+    pub total_assets: Option<Decimal>,
+    pub total_liabilities_net_minority_interest: Option<Decimal>,
+    pub total_equity_gross_minority_interest: Option<Decimal>,
+    pub total_capitalization: Option<Decimal>,
+    pub preferred_stock_equity: Option<Decimal>,
+    pub common_stock_equity: Option<Decimal>,
+    pub net_tangible_assets: Option<Decimal>,
+    pub invested_capital: Option<Decimal>,
+    pub tangible_book_value: Option<Decimal>,
+    pub total_debt: Option<Decimal>,
+    pub net_debt: Option<Decimal>,
+    pub share_issued: Option<Decimal>,
+    pub ordinary_shares_number: Option<Decimal>,
+    pub preferred_shares_number: Option<Decimal>,
+    pub treasury_shares_number: Option<Decimal>,
+    pub working_capital: Option<Decimal>,
+    pub capital_lease_obligations: Option<Decimal>,
+    pub current_assets: Option<Decimal>,
+    pub cash_cash_equivalents_and_short_term_investments: Option<Decimal>,
+    pub cash_and_cash_equivalents: Option<Decimal>,
+    pub other_short_term_investments: Option<Decimal>,
+    pub receivables: Option<Decimal>,
+    pub accounts_receivable: Option<Decimal>,
+    pub inventory: Option<Decimal>,
+    pub total_non_current_assets: Option<Decimal>,
+    pub net_ppe: Option<Decimal>,
+    pub goodwill_and_other_intangible_assets: Option<Decimal>,
+    pub current_liabilities: Option<Decimal>,
+    pub payables_and_accrued_expenses: Option<Decimal>,
+    pub accounts_payable: Option<Decimal>,
+    pub current_debt_and_capital_lease_obligation: Option<Decimal>,
+    pub current_debt: Option<Decimal>,
+    pub total_non_current_liabilities_net_minority_interest: Option<Decimal>,
+    pub long_term_debt_and_capital_lease_obligation: Option<Decimal>,
+    pub long_term_debt: Option<Decimal>,
+    pub stockholders_equity: Option<Decimal>,
+    pub retained_earnings: Option<Decimal>,
+    pub minority_interest: Option<Decimal>,
+    /// Rows with titles that are not known to this version of the crate
+    pub extra: BTreeMap<String, Option<Decimal>>,
+    /// Day the statement was filed, or downloaded when the provider
+    /// does not say
     pub filed: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filing: Option<Filing>,
     pub version: i16,
 }
 
 impl BalanceSheet {
-    pub fn parse(html: &str, symbol: &str) -> Vec<BalanceSheet> {
-        let document = Html::parse_document(html);
-        let rows = Selector::parse(".tableBody .row").unwrap();
-        let mut year1 = vec![];
-        let mut year2 = vec![];
-        let mut year3 = vec![];
-        let mut year4 = vec![];
-        let mut year5 = vec![];
-        let mut titles = vec![];
-        let mut terms = BalanceSheet::get_terms(&document).into_iter();
-        let mut res = vec![];
-
-        for row in document.select(&rows) {
-            let columns_html = row.inner_html();
-            let fragment = Html::parse_fragment(&columns_html);
-            let columns = Selector::parse(".column").unwrap();
-
-            for (column_count, column) in fragment.select(&columns).enumerate() {
-                if column_count == 0 {
-                    let column_html = column.html();
-                    let fragment = Html::parse_fragment(&column_html);
-                    let column_div_selector = Selector::parse(".rowTitle").unwrap();
-                    let content = fragment.select(&column_div_selector).next().unwrap();
-                    let t = content
-                        .text()
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                        .trim()
-                        .to_string();
-
-                    titles.push(t);
-                } else {
-                    let column_html = column.html();
-                    let fragment = Html::parse_fragment(&column_html);
-                    let column_div_selector = Selector::parse("div").unwrap();
-                    let content = fragment.select(&column_div_selector).next().unwrap();
-                    let t = content
-                        .text()
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                        .trim()
-                        .to_string();
-
-                    if column_count == 1 {
-                        year1.push(t);
-                    } else if column_count == 2 {
-                        year2.push(t);
-                    } else if column_count == 3 {
-                        year3.push(t);
-                    } else if column_count == 4 {
-                        year4.push(t);
-                    } else if column_count == 5 {
-                        year5.push(t);
-                    }
-                }
-            }
-        }
-
-        if let Some(term) = terms.next() {
-            res.push(BalanceSheet::from_vec(&titles, year1, &term, symbol));
-        };
-
-        if let Some(term) = terms.next() {
-            res.push(BalanceSheet::from_vec(&titles, year2, &term, symbol));
-        };
-
-        if let Some(term) = terms.next() {
-            res.push(BalanceSheet::from_vec(&titles, year3, &term, symbol));
-        };
-
-        if let Some(term) = terms.next() {
-            res.push(BalanceSheet::from_vec(&titles, year4, &term, symbol));
-        };
-
-        if let Some(term) = terms.next() {
-            res.push(BalanceSheet::from_vec(&titles, year5, &term, symbol));
-        };
-
-        res
+    /// Parse the balance sheets on a downloaded Yahoo page, see [`yahoo::parse`]
+    pub fn parse(
+        html: &str,
+        symbol: &str,
+        period: Period,
+    ) -> Result<Vec<BalanceSheet>, ParseError> {
+        yahoo::parse(html, symbol, period)
     }
+}
 
-    fn get_terms(html: &Html) -> Vec<String> {
-        let headers = Selector::parse(".tableHeader .column").unwrap();
-        let mut titles = vec![];
-
-        for (header_count, header) in html.select(&headers).enumerate() {
-            if header_count != 0 {
-                let text = header
-                    .text()
-                    .collect::<Vec<_>>()
-                    .join(" ")
-                    .trim()
-                    .to_string();
-
-                titles.push(text);
-            }
+impl FinancialStatement for BalanceSheet {
+    const SCHEMA_VERSION: i16 = BALANCE_SHEETS_SCHEMA_VERSION;
+    const FIELDS: &'static [FieldMeta] = fields::FIELDS;
+    const TYPE: StatementType = StatementType::BalanceSheet;
+
+    fn new(symbol: &str, term: NaiveDate, period: Period, kind: TermKind, source: Source) -> Self {
+        BalanceSheet {
+            symbol: symbol.to_string(),
+            term,
+            period,
+            kind,
+            source,
+            filed: chrono::Utc::now().date_naive(),
+            version: BALANCE_SHEETS_SCHEMA_VERSION,
+            ..Default::default()
         }
-
-        titles
     }
 
-    fn from_vec(titles: &[String], values: Vec<String>, term: &str, symbol: &str) -> Self {
-        let mut balance_sheet = BalanceSheet::default();
-        let mut values = values.iter();
-        let current_date = chrono::Utc::now();
-        let year = current_date.year();
-        let month = current_date.month();
-        let day = current_date.day();
-        let term_parser = NaiveDate::parse_from_str;
-        // Don't use unwrap
-        let term = term_parser(term, "%m/%d/%Y").unwrap();
-
-        if let Some(date) = chrono::NaiveDate::from_ymd_opt(year, month, day) {
-            for title in titles.iter() {
-                match title.as_ref() {
-                    "Total Assets" => {
-                        balance_sheet.total_assets = values.next().cloned();
-                    }
-                    "Total Liabilities Net Minority Interest" => {
-                        balance_sheet.total_liabilities_net_minority_interest =
-                            values.next().cloned();
-                    }
-                    "Total Equity Gross Minority Interest" => {
-                        balance_sheet.total_equity_gross_minority_interest = values.next().cloned();
-                    }
-                    "Total Capitalization" => {
-                        balance_sheet.total_capitalization = values.next().cloned();
-                    }
-                    "Preferred Stock Equity" => {
-                        balance_sheet.preferred_stock_equity = values.next().cloned();
-                    }
-                    "Common Stock Equity" => {
-                        balance_sheet.common_stock_equity = values.next().cloned();
-                    }
-                    "Net Tangible Assets" => {
-                        balance_sheet.net_tangible_assets = values.next().cloned();
-                    }
-                    "Invested Capital" => {
-                        balance_sheet.invested_capital = values.next().cloned();
-                    }
-                    "Tangible Book Value" => {
-                        balance_sheet.tangible_book_value = values.next().cloned();
-                    }
-                    "Total Debt" => {
-                        balance_sheet.total_debt = values.next().cloned();
-                    }
-                    "Net Debt" => {
-                        balance_sheet.net_debt = values.next().cloned();
-                    }
-                    "Share Issued" => {
-                        balance_sheet.share_issued = values.next().cloned();
-                    }
-                    "Ordinary Shares Number" => {
-                        balance_sheet.ordinary_shares_number = values.next().cloned();
-                    }
-                    "Preferred Shares Number" => {
-                        balance_sheet.preferred_shares_number = values.next().cloned();
-                    }
-                    "Treasury Shares Number" => {
-                        balance_sheet.treasury_shares_number = values.next().cloned();
-                    }
-                    "Working Capital" => {
-                        balance_sheet.working_capital = values.next().cloned();
-                    }
-                    "Capital Lease Obligations" => {
-                        balance_sheet.capital_lease_obligations = values.next().cloned();
-                    }
-                    &_ => {
-                        println!(">>>>>>>> New field (Balance Sheet): {title}");
-                    }
-                }
-            }
-            balance_sheet.symbol = symbol.to_string();
-            balance_sheet.term = term;
-            balance_sheet.filed = date;
-            balance_sheet.version = BALANCE_SHEETS_SCHEMA_VERSION;
-            balance_sheet
-        } else {
-            // TODO: do not panic
-            panic!("Date error");
-        }
+    fn download(provider: &dyn Provider, symbol: &str, period: Period) -> Result<Vec<Self>, Error> {
+        provider.balance_sheets(symbol, period)
     }
 
-    pub fn hash(&self) -> String {
-        let mut hasher = blake3::Hasher::new();
-
-        hasher.update(self.symbol.to_string().as_bytes());
-        hasher.update(self.term.to_string().as_bytes());
-
-        if let Some(total_assets) = &self.total_assets {
-            hasher.update(total_assets.as_bytes());
-        }
-
-        if let Some(total_liabilities_net_minority_interest) =
-            &self.total_liabilities_net_minority_interest
-        {
-            hasher.update(total_liabilities_net_minority_interest.as_bytes());
-        }
-
-        if let Some(total_equity_gross_minority_interest) =
-            &self.total_equity_gross_minority_interest
-        {
-            hasher.update(total_equity_gross_minority_interest.as_bytes());
-        }
-
-        if let Some(total_capitalization) = &self.total_capitalization {
-            hasher.update(total_capitalization.as_bytes());
-        }
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
 
-        if let Some(preferred_stock_equity) = &self.preferred_stock_equity {
-            hasher.update(preferred_stock_equity.as_bytes());
-        }
+    fn term(&self) -> NaiveDate {
+        self.term
+    }
 
-        if let Some(common_stock_equity) = &self.common_stock_equity {
-            hasher.update(common_stock_equity.as_bytes());
-        }
+    fn period(&self) -> Period {
+        self.period
+    }
 
-        if let Some(net_tangible_assets) = &self.net_tangible_assets {
-            hasher.update(net_tangible_assets.as_bytes());
-        }
+    fn kind(&self) -> TermKind {
+        self.kind
+    }
 
-        if let Some(invested_capital) = &self.invested_capital {
-            hasher.update(invested_capital.as_bytes());
-        }
+    fn source(&self) -> &Source {
+        &self.source
+    }
 
-        if let Some(tangible_book_value) = &self.tangible_book_value {
-            hasher.update(tangible_book_value.as_bytes());
-        }
+    fn filed(&self) -> NaiveDate {
+        self.filed
+    }
 
-        if let Some(total_debt) = &self.total_debt {
-            hasher.update(total_debt.as_bytes());
-        }
+    fn filed_mut(&mut self) -> &mut NaiveDate {
+        &mut self.filed
+    }
 
-        if let Some(net_debt) = &self.net_debt {
-            hasher.update(net_debt.as_bytes());
-        }
+    fn filing(&self) -> Option<&Filing> {
+        self.filing.as_ref()
+    }
 
-        if let Some(share_issued) = &self.share_issued {
-            hasher.update(share_issued.as_bytes());
-        }
+    fn filing_mut(&mut self) -> &mut Option<Filing> {
+        &mut self.filing
+    }
 
-        if let Some(ordinary_shares_number) = &self.ordinary_shares_number {
-            hasher.update(ordinary_shares_number.as_bytes());
-        }
+    fn version(&self) -> i16 {
+        self.version
+    }
 
-        if let Some(preferred_shares_number) = &self.preferred_shares_number {
-            hasher.update(preferred_shares_number.as_bytes());
-        }
+    fn version_mut(&mut self) -> &mut i16 {
+        &mut self.version
+    }
 
-        if let Some(treasury_shares_number) = &self.treasury_shares_number {
-            hasher.update(treasury_shares_number.as_bytes());
-        }
+    fn fields(&self) -> Vec<(&'static str, Option<Decimal>)> {
+        vec![
+            ("total_assets", self.total_assets),
+            (
+                "total_liabilities_net_minority_interest",
+                self.total_liabilities_net_minority_interest,
+            ),
+            (
+                "total_equity_gross_minority_interest",
+                self.total_equity_gross_minority_interest,
+            ),
+            ("total_capitalization", self.total_capitalization),
+            ("preferred_stock_equity", self.preferred_stock_equity),
+            ("common_stock_equity", self.common_stock_equity),
+            ("net_tangible_assets", self.net_tangible_assets),
+            ("invested_capital", self.invested_capital),
+            ("tangible_book_value", self.tangible_book_value),
+            ("total_debt", self.total_debt),
+            ("net_debt", self.net_debt),
+            ("share_issued", self.share_issued),
+            ("ordinary_shares_number", self.ordinary_shares_number),
+            ("preferred_shares_number", self.preferred_shares_number),
+            ("treasury_shares_number", self.treasury_shares_number),
+            ("working_capital", self.working_capital),
+            ("capital_lease_obligations", self.capital_lease_obligations),
+            ("current_assets", self.current_assets),
+            (
+                "cash_cash_equivalents_and_short_term_investments",
+                self.cash_cash_equivalents_and_short_term_investments,
+            ),
+            ("cash_and_cash_equivalents", self.cash_and_cash_equivalents),
+            (
+                "other_short_term_investments",
+                self.other_short_term_investments,
+            ),
+            ("receivables", self.receivables),
+            ("accounts_receivable", self.accounts_receivable),
+            ("inventory", self.inventory),
+            ("total_non_current_assets", self.total_non_current_assets),
+            ("net_ppe", self.net_ppe),
+            (
+                "goodwill_and_other_intangible_assets",
+                self.goodwill_and_other_intangible_assets,
+            ),
+            ("current_liabilities", self.current_liabilities),
+            (
+                "payables_and_accrued_expenses",
+                self.payables_and_accrued_expenses,
+            ),
+            ("accounts_payable", self.accounts_payable),
+            (
+                "current_debt_and_capital_lease_obligation",
+                self.current_debt_and_capital_lease_obligation,
+            ),
+            ("current_debt", self.current_debt),
+            (
+                "total_non_current_liabilities_net_minority_interest",
+                self.total_non_current_liabilities_net_minority_interest,
+            ),
+            (
+                "long_term_debt_and_capital_lease_obligation",
+                self.long_term_debt_and_capital_lease_obligation,
+            ),
+            ("long_term_debt", self.long_term_debt),
+            ("stockholders_equity", self.stockholders_equity),
+            ("retained_earnings", self.retained_earnings),
+            ("minority_interest", self.minority_interest),
+        ]
+    }
 
-        if let Some(working_capital) = &self.working_capital {
-            hasher.update(working_capital.as_bytes());
+    fn field_mut(&mut self, name: &str) -> Option<&mut Option<Decimal>> {
+        match name {
+            "total_assets" => Some(&mut self.total_assets),
+            "total_liabilities_net_minority_interest" => {
+                Some(&mut self.total_liabilities_net_minority_interest)
+            }
+            "total_equity_gross_minority_interest" => {
+                Some(&mut self.total_equity_gross_minority_interest)
+            }
+            "total_capitalization" => Some(&mut self.total_capitalization),
+            "preferred_stock_equity" => Some(&mut self.preferred_stock_equity),
+            "common_stock_equity" => Some(&mut self.common_stock_equity),
+            "net_tangible_assets" => Some(&mut self.net_tangible_assets),
+            "invested_capital" => Some(&mut self.invested_capital),
+            "tangible_book_value" => Some(&mut self.tangible_book_value),
+            "total_debt" => Some(&mut self.total_debt),
+            "net_debt" => Some(&mut self.net_debt),
+            "share_issued" => Some(&mut self.share_issued),
+            "ordinary_shares_number" => Some(&mut self.ordinary_shares_number),
+            "preferred_shares_number" => Some(&mut self.preferred_shares_number),
+            "treasury_shares_number" => Some(&mut self.treasury_shares_number),
+            "working_capital" => Some(&mut self.working_capital),
+            "capital_lease_obligations" => Some(&mut self.capital_lease_obligations),
+            "current_assets" => Some(&mut self.current_assets),
+            "cash_cash_equivalents_and_short_term_investments" => {
+                Some(&mut self.cash_cash_equivalents_and_short_term_investments)
+            }
+            "cash_and_cash_equivalents" => Some(&mut self.cash_and_cash_equivalents),
+            "other_short_term_investments" => Some(&mut self.other_short_term_investments),
+            "receivables" => Some(&mut self.receivables),
+            "accounts_receivable" => Some(&mut self.accounts_receivable),
+            "inventory" => Some(&mut self.inventory),
+            "total_non_current_assets" => Some(&mut self.total_non_current_assets),
+            "net_ppe" => Some(&mut self.net_ppe),
+            "goodwill_and_other_intangible_assets" => {
+                Some(&mut self.goodwill_and_other_intangible_assets)
+            }
+            "current_liabilities" => Some(&mut self.current_liabilities),
+            "payables_and_accrued_expenses" => Some(&mut self.payables_and_accrued_expenses),
+            "accounts_payable" => Some(&mut self.accounts_payable),
+            "current_debt_and_capital_lease_obligation" => {
+                Some(&mut self.current_debt_and_capital_lease_obligation)
+            }
+            "current_debt" => Some(&mut self.current_debt),
+            "total_non_current_liabilities_net_minority_interest" => {
+                Some(&mut self.total_non_current_liabilities_net_minority_interest)
+            }
+            "long_term_debt_and_capital_lease_obligation" => {
+                Some(&mut self.long_term_debt_and_capital_lease_obligation)
+            }
+            "long_term_debt" => Some(&mut self.long_term_debt),
+            "stockholders_equity" => Some(&mut self.stockholders_equity),
+            "retained_earnings" => Some(&mut self.retained_earnings),
+            "minority_interest" => Some(&mut self.minority_interest),
+            _ => None,
         }
+    }
 
-        if let Some(capital_lease_obligations) = &self.capital_lease_obligations {
-            hasher.update(capital_lease_obligations.as_bytes());
-        }
+    fn extra(&self) -> &BTreeMap<String, Option<Decimal>> {
+        &self.extra
+    }
 
-        let hash = hasher.finalize();
-        BASE64_STANDARD.encode(hash.as_bytes())
+    fn extra_mut(&mut self) -> &mut BTreeMap<String, Option<Decimal>> {
+        &mut self.extra
     }
 }
+
 impl Spider for BalanceSheet {
-    fn fetch(symbol: &str) -> Result<String, reqwest::Error> {
-        let url = format!("{YAHOO_ROOT}/quote/{symbol}/balance-sheet");
-        println!("---> Fetching Balance Sheet for: {symbol}");
-        println!("---> {url}");
-        let client = reqwest::blocking::Client::builder()
-            .user_agent(USER_AGENT)
-            .build()?;
-        let html = client.get(url).send()?.text()?;
-        Ok(html)
+    const NAME: &'static str = "Balance Sheet";
+
+    fn url(root: &str, symbol: &str, period: Period) -> String {
+        yahoo::url(root, Self::TYPE, symbol, period)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::SpiderClient;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/yahoo");
 
     #[test]
     fn test_balance_sheets_standard_bank() {
         let symbol = "SBKP.JO";
-        let html = BalanceSheet::fetch(symbol).unwrap();
-        let balance_sheets: Vec<BalanceSheet> = BalanceSheet::parse(&html, symbol);
+        let client = SpiderClient::builder().replay(FIXTURES).build().unwrap();
+        let html = client
+            .fetch::<BalanceSheet>(symbol, Period::Annual)
+            .unwrap();
+        let balance_sheets: Vec<BalanceSheet> =
+            BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
         assert_eq!(balance_sheets.len(), 5);
         assert_eq!(
             balance_sheets[0].total_assets,
-            Some("3,065,745,000.00".to_string())
+            Some(Decimal::from_str_exact("3065745000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].total_liabilities_net_minority_interest,
-            Some("2,788,825,000.00".to_string())
+            Some(Decimal::from_str_exact("2788825000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].total_equity_gross_minority_interest,
-            Some("276,920,000.00".to_string())
+            Some(Decimal::from_str_exact("276920000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].total_capitalization,
-            Some("393,537,000.00".to_string())
+            Some(Decimal::from_str_exact("393537000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].preferred_stock_equity,
-            Some("5,503,000.00".to_string())
+            Some(Decimal::from_str_exact("5503000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].common_stock_equity,
-            Some("255,109,000.00".to_string())
+            Some(Decimal::from_str_exact("255109000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].net_tangible_assets,
-            Some("247,889,000.00".to_string())
+            Some(Decimal::from_str_exact("247889000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].invested_capital,
-            Some("388,034,000.00".to_string())
+            Some(Decimal::from_str_exact("388034000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].tangible_book_value,
-            Some("242,386,000.00".to_string())
+            Some(Decimal::from_str_exact("242386000.00").unwrap())
         );
         assert_eq!(
             balance_sheets[0].total_debt,
-            Some("136,639,000.00".to_string())
+            Some(Decimal::from_str_exact("136639000.00").unwrap())
         );
-        assert_eq!(balance_sheets[0].net_debt, Some("--".to_string()));
+        assert_eq!(balance_sheets[0].net_debt, None);
         assert_eq!(
             balance_sheets[0].share_issued,
-            Some("1,675,775.23".to_string())
+            Some(Decimal::from_str_exact("1675775.23").unwrap())
         );
         assert_eq!(
             balance_sheets[0].ordinary_shares_number,
-            Some("1,657,074.12".to_string())
+            Some(Decimal::from_str_exact("1657074.12").unwrap())
         );
         assert_eq!(
             balance_sheets[0].preferred_shares_number,
-            Some("60,982.25".to_string())
+            Some(Decimal::from_str_exact("60982.25").unwrap())
         );
         assert_eq!(
             balance_sheets[0].treasury_shares_number,
-            Some("18,701.11".to_string())
+            Some(Decimal::from_str_exact("18701.11").unwrap())
         );
 
         assert_eq!(balance_sheets[0].working_capital, None);
@@ -389,90 +374,189 @@ mod tests {
         assert_eq!(balance_sheets[0].capital_lease_obligations, None);
     }
 
+    #[test]
+    fn test_balance_sheets_parse() {
+        let symbol = "SBKP.JO";
+        let page = |term: &str, value: &str| {
+            format!(
+                r#"<div class="tableHeader"><div class="column">Breakdown</div><div class="column">{term}</div></div>
+<div class="tableBody"><div class="row"><div class="column"><div class="rowTitle">Total Assets</div></div><div class="column"><div>{value}</div></div></div>
+<div class="row"><div class="column"><div class="rowTitle">Brand New Row</div></div><div class="column"><div>5.00</div></div></div></div>"#
+            )
+        };
+
+        let balance_sheets =
+            BalanceSheet::parse(&page("12/31/2023", "1,000.00"), symbol, Period::Annual).unwrap();
+        assert_eq!(balance_sheets.len(), 1);
+        assert_eq!(
+            balance_sheets[0].total_assets,
+            Some(Decimal::new(100000, 2))
+        );
+
+        // Without a date for the TTM column it is left out
+        assert_eq!(
+            BalanceSheet::parse(&page("TTM", "1,000.00"), symbol, Period::Annual),
+            Ok(vec![])
+        );
+        assert_eq!(
+            BalanceSheet::parse(&page("Sep 2023", "1,000.00"), symbol, Period::Annual),
+            Err(ParseError::Term("Sep 2023".to_string()))
+        );
+
+        // Pages without an embedded timeseries date it as of the latest term
+        let html = page("TTM</div><div class=\"column\">12/31/2023", "1,000.00");
+        let balance_sheets = BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
+        assert_eq!(balance_sheets[0].kind, TermKind::Ttm);
+        assert_eq!(
+            balance_sheets[0].term,
+            NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+        );
+        assert_eq!(
+            BalanceSheet::parse(&page("12/31/2023", "n/a"), symbol, Period::Annual),
+            Err(ParseError::Value {
+                title: "Total Assets".to_string(),
+                term: "12/31/2023".to_string(),
+                text: "n/a".to_string(),
+            })
+        );
+
+        // Sub-rows, both nested in their parent and as siblings of it
+        let expanded = r#"<div class="tableHeader"><div class="column">Breakdown</div><div class="column">12/31/2023</div></div>
+<div class="tableBody"><div class="row lv-0"><div class="column"><div class="rowTitle">Total Assets</div></div><div class="column"><div>1,000.00</div></div>
+<div class="row lv-1"><div class="column"><div class="rowTitle">Current Assets</div></div><div class="column"><div>400.00</div></div>
+<div class="row lv-2"><div class="column"><div class="rowTitle">Cash And Cash Equivalents</div></div><div class="column"><div>150.00</div></div></div>
+<div class="row"><div class="column"><div class="rowTitle">Prepaid Expenses</div></div><div class="column"><div>7.00</div></div></div></div></div>
+<div class="row lv-1"><div class="column"><div class="rowTitle">Total non-current assets</div></div><div class="column"><div>600.00</div></div></div>
+<div class="row lv-2"><div class="column"><div class="rowTitle">Net PPE</div></div><div class="column"><div>--</div></div></div>
+<div class="row lv-3"><div class="column"><div class="rowTitle">Land</div></div><div class="column"><div>80.00</div></div></div></div>"#;
+
+        let balance_sheets = BalanceSheet::parse(expanded, symbol, Period::Annual).unwrap();
+        assert_eq!(
+            balance_sheets[0].total_assets,
+            Some(Decimal::new(100000, 2))
+        );
+        assert_eq!(
+            balance_sheets[0].current_assets,
+            Some(Decimal::new(40000, 2))
+        );
+        assert_eq!(
+            balance_sheets[0].cash_and_cash_equivalents,
+            Some(Decimal::new(15000, 2))
+        );
+        assert_eq!(
+            balance_sheets[0].total_non_current_assets,
+            Some(Decimal::new(60000, 2))
+        );
+        assert_eq!(balance_sheets[0].net_ppe, None);
+
+        // Unknown sub-rows keep the rows they are part of
+        assert_eq!(
+            balance_sheets[0].extra.keys().collect::<Vec<_>>(),
+            [
+                "Total Assets > Current Assets > Prepaid Expenses",
+                "Total Assets > Total non-current assets > Net PPE > Land"
+            ]
+        );
+        assert_eq!(
+            balance_sheets[0].field("Total Assets > Current Assets > Prepaid Expenses"),
+            Some(Decimal::new(700, 2))
+        );
+
+        let missing_title = r#"<div class="tableBody"><div class="row"><div class="column">Total Assets</div></div></div>"#;
+        assert!(matches!(
+            BalanceSheet::parse(missing_title, symbol, Period::Annual),
+            Err(ParseError::Element {
+                row: 0,
+                column: 0,
+                ..
+            })
+        ));
+    }
+
     // #[test]
     // fn test_multiple_balance_sheets() {
     //     let symbol = "avgo";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "kfy";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "bili";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "bbar";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "cepu";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "tgs";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "vrt";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "bma";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "mcw";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "cc";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "teo";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "rytm";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "hph";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "tal";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "ibrx";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "incy";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "ymm";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "cgnx";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "qrvo";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "pam";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
 
     //     let symbol = "srpt";
-    //     let html = BalanceSheet::fetch(symbol).unwrap();
-    //     BalanceSheet::parse(&html, symbol);
+    //     let html = BalanceSheet::fetch(symbol, Period::Annual).unwrap();
+    //     BalanceSheet::parse(&html, symbol, Period::Annual).unwrap();
     // }
 }
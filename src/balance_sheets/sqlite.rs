@@ -0,0 +1,98 @@
+use super::BalanceSheet;
+use crate::error::Error;
+use crate::sqlite;
+use crate::store::{OnConflict, Saved};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::Connection;
+
+/// SQLite storage for balance sheets, with the same semantics as the
+/// PostgreSQL one
+pub struct BalanceSheetsSqlite;
+impl BalanceSheetsSqlite {
+    /// Initialize SQLite cache
+    pub fn init(conn: &Connection, table_name: &str) -> Result<(), Error> {
+        sqlite::init::<BalanceSheet>(conn, table_name)
+    }
+
+    /// Save balance sheet in database
+    pub fn save(
+        conn: &Connection,
+        table_name: &str,
+        balance_sheet: BalanceSheet,
+    ) -> Result<(), Error> {
+        sqlite::save(conn, table_name, &balance_sheet)
+    }
+
+    /// Save balance sheet in database, doing `on_conflict` when one with the
+    /// same hash is already stored
+    pub fn upsert(
+        conn: &Connection,
+        table_name: &str,
+        balance_sheet: BalanceSheet,
+        on_conflict: OnConflict,
+    ) -> Result<Saved, Error> {
+        sqlite::upsert(conn, table_name, &balance_sheet, on_conflict)
+    }
+
+    pub fn read(
+        conn: &Connection,
+        table_name: &str,
+        hash: &str,
+    ) -> Result<Option<BalanceSheet>, Error> {
+        sqlite::read(conn, table_name, hash)
+    }
+
+    pub fn read_all(conn: &Connection, table_name: &str) -> Result<Vec<BalanceSheet>, Error> {
+        sqlite::read_all(conn, table_name)
+    }
+
+    pub fn read_all_by_symbol(
+        conn: &Connection,
+        table_name: &str,
+        symbol: &str,
+    ) -> Result<Vec<BalanceSheet>, Error> {
+        sqlite::read_all_by_symbol(conn, table_name, symbol)
+    }
+
+    /// Read the balance sheets of `symbol` with a term from `from` to `to`,
+    /// oldest first
+    pub fn read_range(
+        conn: &Connection,
+        table_name: &str,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<BalanceSheet>, Error> {
+        sqlite::read_range(conn, table_name, symbol, from, to)
+    }
+
+    /// Save balance sheet in database as the current version of its term from
+    /// `at`, moving the version it restates to the history
+    pub fn revise(
+        conn: &Connection,
+        table_name: &str,
+        balance_sheet: BalanceSheet,
+        at: DateTime<Utc>,
+    ) -> Result<Saved, Error> {
+        sqlite::revise(conn, table_name, &balance_sheet, at)
+    }
+
+    /// Read the balance sheets of `symbol` with a term from `from` to `to` as
+    /// they were stored at `at`, oldest first
+    pub fn read_as_of(
+        conn: &Connection,
+        table_name: &str,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<BalanceSheet>, Error> {
+        sqlite::read_as_of(conn, table_name, symbol, from, to, at)
+    }
+
+    /// Delete the statement identified by `hash`, returning whether it
+    /// was stored
+    pub fn delete(conn: &Connection, table_name: &str, hash: &str) -> Result<bool, Error> {
+        sqlite::delete(conn, table_name, hash)
+    }
+}
@@ -0,0 +1,141 @@
+//! On-disk cache of downloaded pages
+//!
+//! Pages are stored in a directory, one file per url, named after the
+//! blake3 hash of the url. The age of an entry is taken from the file's
+//! modification time.
+
+use crate::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default time a cached page is considered fresh
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Directory of cached pages
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+    ttl: Duration,
+    force_refresh: bool,
+    stale_if_error: bool,
+}
+
+impl Cache {
+    /// Cache pages in `dir` for [`DEFAULT_TTL`], serving stale pages
+    /// when a download fails
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache {
+            dir: dir.into(),
+            ttl: DEFAULT_TTL,
+            force_refresh: false,
+            stale_if_error: true,
+        }
+    }
+
+    /// Time a cached page is considered fresh
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Always download pages, only using the cache as a fallback
+    pub fn force_refresh(mut self, force_refresh: bool) -> Self {
+        self.force_refresh = force_refresh;
+        self
+    }
+
+    /// Serve an expired page when downloading a fresh one fails
+    pub fn stale_if_error(mut self, stale_if_error: bool) -> Self {
+        self.stale_if_error = stale_if_error;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Cache key of `url`
+    pub fn key(url: &str) -> String {
+        blake3::hash(url.as_bytes()).to_hex().to_string()
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{}.html", Cache::key(url)))
+    }
+
+    /// Page cached for `url`, unless it expired or a refresh is forced
+    pub fn fresh(&self, url: &str) -> Option<String> {
+        if self.force_refresh {
+            return None;
+        }
+
+        let path = self.path(url);
+        let age = fs::metadata(&path).ok()?.modified().ok()?.elapsed().ok()?;
+
+        if age <= self.ttl {
+            fs::read_to_string(path).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Page cached for `url` regardless of its age, if stale pages may
+    /// be served
+    pub fn stale(&self, url: &str) -> Option<String> {
+        if self.stale_if_error {
+            fs::read_to_string(self.path(url)).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Cache `html` as the page for `url`
+    pub fn store(&self, url: &str, html: &str) -> Result<(), Error> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(url), html)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::retry::RetryPolicy;
+    use crate::SpiderClient;
+
+    #[test]
+    fn test_cache() {
+        let dir = std::env::temp_dir().join(format!("finspider-cache-{}", std::process::id()));
+        let server = MockServer::start(vec![MockResponse::ok("<html>1</html>")]);
+        let url = format!("{}/quote/SBKP.JO/balance-sheet", server.url);
+
+        let client = SpiderClient::builder()
+            .cache(Cache::new(&dir))
+            .build()
+            .unwrap();
+
+        // Downloaded, then served from the cache
+        assert_eq!(client.get(&url).unwrap(), "<html>1</html>");
+        server.requests();
+        assert_eq!(client.get(&url).unwrap(), "<html>1</html>");
+
+        // The server is gone, the stale page is served instead
+        let client = SpiderClient::builder()
+            .cache(Cache::new(&dir).force_refresh(true))
+            .retry(RetryPolicy::none())
+            .build()
+            .unwrap();
+        assert_eq!(client.get(&url).unwrap(), "<html>1</html>");
+
+        let client = SpiderClient::builder()
+            .cache(Cache::new(&dir).ttl(Duration::ZERO).stale_if_error(false))
+            .retry(RetryPolicy::none())
+            .build()
+            .unwrap();
+        assert!(matches!(client.get(&url), Err(Error::Fetch(_))));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
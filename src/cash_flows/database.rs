@@ -1,102 +1,33 @@
 use super::CashFlow;
+use crate::database;
 use crate::error::Error;
+use crate::store::{OnConflict, Saved};
+use chrono::{DateTime, NaiveDate, Utc};
 use postgres::Client;
 
-/// Database management for cash flow
+/// Database management for cash flows
 pub struct CashFlowDB;
 impl CashFlowDB {
-    /// Initialize postgres cache
+    /// Initialize postgres cache, migrating tables created by older
+    /// versions
     pub fn init(client: &mut Client, table_name: &str, db_owner: &str) -> Result<(), Error> {
-        let sql = format!(
-            "
-CREATE TABLE IF NOT EXISTS {table_name} (
-	id serial NOT NULL,
-        symbol varchar(80) NOT NULL,
-        term date NOT NULL,
-        cash_flows_from_used_in_operating_activities_direct varchar(100),
-        operating_cash_flow varchar(100),
-        investing_cash_flow varchar(100),
-        financing_cash_flow varchar(100),
-        end_cash_position varchar(100),
-        capital_expenditure varchar(100),
-        issuance_of_capital_stock varchar(100),
-        issuance_of_debt varchar(100),
-        repayment_of_debt varchar(100),
-        repurchase_of_capital_stock varchar(100),
-        free_cash_flow varchar(100),
-        income_tax_paid_supplemental_data varchar(100),
-        interest_paid_supplemental_data varchar(100),
-        other_cash_adjustment_inside_change_in_cash varchar(100),
-        filed date NOT NULL,
-        hash text UNIQUE NOT NULL,
-        version smallint NOT NULL
-);
--- ddl-end --
-ALTER TABLE {table_name} OWNER TO {db_owner};
--- ddl-end --"
-        );
-        client
-            .batch_execute(&sql)
-            .map_err(|_| Error::TableCreation)?;
-
-        Ok(())
+        database::init::<CashFlow>(client, table_name, db_owner)
     }
 
-    /// Save cash sheet in database
+    /// Save cash flow in database
     pub fn save(client: &mut Client, table_name: &str, cash_flow: CashFlow) -> Result<(), Error> {
-        let sql = format!(
-            "INSERT INTO {table_name} (
-        symbol,
-        term,
-        cash_flows_from_used_in_operating_activities_direct,
-        operating_cash_flow,
-        investing_cash_flow,
-        financing_cash_flow,
-        end_cash_position,
-        capital_expenditure,
-        issuance_of_capital_stock,
-        issuance_of_debt,
-        repayment_of_debt,
-        repurchase_of_capital_stock,
-        free_cash_flow,
-        income_tax_paid_supplemental_data,
-        interest_paid_supplemental_data,
-        other_cash_adjustment_inside_change_in_cash,
-        filed,
-        hash,
-        version
-)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
-                         $12, $13, $14, $15, $16, $17, $18, $19);"
-        );
+        database::save(client, table_name, &cash_flow)
+    }
 
-        client
-            .execute(
-                &sql,
-                &[
-                    &cash_flow.symbol,
-                    &cash_flow.term,
-                    &cash_flow.cash_flows_from_used_in_operating_activities_direct,
-                    &cash_flow.operating_cash_flow,
-                    &cash_flow.investing_cash_flow,
-                    &cash_flow.financing_cash_flow,
-                    &cash_flow.end_cash_position,
-                    &cash_flow.capital_expenditure,
-                    &cash_flow.issuance_of_capital_stock,
-                    &cash_flow.issuance_of_debt,
-                    &cash_flow.repayment_of_debt,
-                    &cash_flow.repurchase_of_capital_stock,
-                    &cash_flow.free_cash_flow,
-                    &cash_flow.income_tax_paid_supplemental_data,
-                    &cash_flow.interest_paid_supplemental_data,
-                    &cash_flow.other_cash_adjustment_inside_change_in_cash,
-                    &cash_flow.filed,
-                    &cash_flow.hash(),
-                    &cash_flow.version,
-                ],
-            )
-            .map_err(|_| Error::SQL)?;
-        Ok(())
+    /// Save cash flow in database, doing `on_conflict` when one with the
+    /// same hash is already stored
+    pub fn upsert(
+        client: &mut Client,
+        table_name: &str,
+        cash_flow: CashFlow,
+        on_conflict: OnConflict,
+    ) -> Result<Saved, Error> {
+        database::upsert(client, table_name, &cash_flow, on_conflict)
     }
 
     pub fn read(
@@ -104,68 +35,11 @@ ALTER TABLE {table_name} OWNER TO {db_owner};
         table_name: &str,
         hash: &str,
     ) -> Result<Option<CashFlow>, Error> {
-        let sql = format!("SELECT * FROM {table_name} WHERE hash = $1");
-        let row = client.query(&sql, &[&hash]).map_err(|_| Error::SQL)?;
-
-        if row.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(CashFlow {
-                symbol: row[0].get("symbol"),
-                term: row[0].get("term"),
-                cash_flows_from_used_in_operating_activities_direct: row[0]
-                    .get("cash_flows_from_used_in_operating_activities_direct"),
-                operating_cash_flow: row[0].get("operating_cash_flow"),
-                investing_cash_flow: row[0].get("investing_cash_flow"),
-                financing_cash_flow: row[0].get("financing_cash_flow"),
-                end_cash_position: row[0].get("end_cash_position"),
-                capital_expenditure: row[0].get("capital_expenditure"),
-                issuance_of_capital_stock: row[0].get("issuance_of_capital_stock"),
-                issuance_of_debt: row[0].get("issuance_of_debt"),
-                repayment_of_debt: row[0].get("repayment_of_debt"),
-                repurchase_of_capital_stock: row[0].get("repurchase_of_capital_stock"),
-                free_cash_flow: row[0].get("free_cash_flow"),
-                income_tax_paid_supplemental_data: row[0].get("income_tax_paid_supplemental_data"),
-                interest_paid_supplemental_data: row[0].get("interest_paid_supplemental_data"),
-                other_cash_adjustment_inside_change_in_cash: row[0]
-                    .get("other_cash_adjustment_inside_change_in_cash"),
-                version: row[0].get("version"),
-                filed: row[0].get("filed"),
-            }))
-        }
+        database::read(client, table_name, hash)
     }
 
     pub fn read_all(client: &mut Client, table_name: &str) -> Result<Vec<CashFlow>, Error> {
-        let sql = format!("SELECT * FROM {table_name}");
-        let row = client.query(&sql, &[]).map_err(|_| Error::SQL)?;
-        let mut cash_flow: Vec<CashFlow> = vec![];
-
-        for r in &row {
-            cash_flow.push(CashFlow {
-                symbol: r.get("symbol"),
-                term: r.get("term"),
-                cash_flows_from_used_in_operating_activities_direct: r
-                    .get("cash_flows_from_used_in_operating_activities_direct"),
-                operating_cash_flow: r.get("operating_cash_flow"),
-                investing_cash_flow: r.get("investing_cash_flow"),
-                financing_cash_flow: r.get("financing_cash_flow"),
-                end_cash_position: r.get("end_cash_position"),
-                capital_expenditure: r.get("capital_expenditure"),
-                issuance_of_capital_stock: r.get("issuance_of_capital_stock"),
-                issuance_of_debt: r.get("issuance_of_debt"),
-                repayment_of_debt: r.get("repayment_of_debt"),
-                repurchase_of_capital_stock: r.get("repurchase_of_capital_stock"),
-                free_cash_flow: r.get("free_cash_flow"),
-                income_tax_paid_supplemental_data: r.get("income_tax_paid_supplemental_data"),
-                interest_paid_supplemental_data: r.get("interest_paid_supplemental_data"),
-                other_cash_adjustment_inside_change_in_cash: r
-                    .get("other_cash_adjustment_inside_change_in_cash"),
-                filed: r.get("filed"),
-                version: r.get("version"),
-            })
-        }
-
-        Ok(cash_flow)
+        database::read_all(client, table_name)
     }
 
     pub fn read_all_by_symbol(
@@ -173,46 +47,62 @@ ALTER TABLE {table_name} OWNER TO {db_owner};
         table_name: &str,
         symbol: &str,
     ) -> Result<Vec<CashFlow>, Error> {
-        let sql = format!("SELECT * FROM {table_name} WHERE symbol = $1");
-        let row = client.query(&sql, &[&symbol]).map_err(|_| Error::SQL)?;
-        let mut cash_flow: Vec<CashFlow> = vec![];
+        database::read_all_by_symbol(client, table_name, symbol)
+    }
 
-        for r in &row {
-            cash_flow.push(CashFlow {
-                symbol: r.get("symbol"),
-                term: r.get("term"),
-                cash_flows_from_used_in_operating_activities_direct: r
-                    .get("cash_flows_from_used_in_operating_activities_direct"),
-                operating_cash_flow: r.get("operating_cash_flow"),
-                investing_cash_flow: r.get("investing_cash_flow"),
-                financing_cash_flow: r.get("financing_cash_flow"),
-                end_cash_position: r.get("end_cash_position"),
-                capital_expenditure: r.get("capital_expenditure"),
-                issuance_of_capital_stock: r.get("issuance_of_capital_stock"),
-                issuance_of_debt: r.get("issuance_of_debt"),
-                repayment_of_debt: r.get("repayment_of_debt"),
-                repurchase_of_capital_stock: r.get("repurchase_of_capital_stock"),
-                free_cash_flow: r.get("free_cash_flow"),
-                income_tax_paid_supplemental_data: r.get("income_tax_paid_supplemental_data"),
-                interest_paid_supplemental_data: r.get("interest_paid_supplemental_data"),
-                other_cash_adjustment_inside_change_in_cash: r
-                    .get("other_cash_adjustment_inside_change_in_cash"),
-                filed: r.get("filed"),
-                version: r.get("version"),
-            })
-        }
+    /// Read the cash flows of `symbol` with a term from `from` to `to`,
+    /// oldest first
+    pub fn read_range(
+        client: &mut Client,
+        table_name: &str,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<CashFlow>, Error> {
+        database::read_range(client, table_name, symbol, from, to)
+    }
+
+    /// Delete the statement identified by `hash`, returning whether it
+    /// was stored
+    pub fn delete(client: &mut Client, table_name: &str, hash: &str) -> Result<bool, Error> {
+        database::delete(client, table_name, hash)
+    }
 
-        Ok(cash_flow)
+    /// Save cash flow in database as the current version of its term from
+    /// `at`, moving the version it restates to the history
+    pub fn revise(
+        client: &mut Client,
+        table_name: &str,
+        cash_flow: CashFlow,
+        at: DateTime<Utc>,
+    ) -> Result<Saved, Error> {
+        database::revise(client, table_name, &cash_flow, at)
+    }
+
+    /// Read the cash flows of `symbol` with a term from `from` to `to` as
+    /// they were stored at `at`, oldest first
+    pub fn read_as_of(
+        client: &mut Client,
+        table_name: &str,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<CashFlow>, Error> {
+        database::read_as_of(client, table_name, symbol, from, to, at)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::statement::Filing;
+    use crate::{Decimal, FinancialStatement, Period, Source, TermKind};
     use chrono::Datelike;
     use postgres::{Client, NoTls};
+    use std::collections::BTreeMap;
     use std::env;
-    const TABLE: &'static str = "cash_flows_test_database";
+    const TABLE: &str = "cash_flows_test_database";
 
     /// Test the routines for the cash flow db
     #[test]
@@ -240,21 +130,37 @@ mod tests {
         let cash_flow = CashFlow {
             symbol: "SBKP.JO".to_string(),
             term: date,
-            cash_flows_from_used_in_operating_activities_direct: Some("1000.00".to_string()),
-            operating_cash_flow: Some("1000.00".to_string()),
-            investing_cash_flow: Some("1000.00".to_string()),
-            financing_cash_flow: Some("1000.00".to_string()),
-            end_cash_position: Some("1000.00".to_string()),
-            capital_expenditure: Some("1000.00".to_string()),
-            issuance_of_capital_stock: Some("1000.00".to_string()),
-            issuance_of_debt: Some("1000.00".to_string()),
-            repayment_of_debt: Some("1000.00".to_string()),
-            repurchase_of_capital_stock: Some("1000.00".to_string()),
-            free_cash_flow: Some("1000.00".to_string()),
-            income_tax_paid_supplemental_data: Some("1000.00".to_string()),
-            interest_paid_supplemental_data: Some("1000.00".to_string()),
-            other_cash_adjustment_inside_change_in_cash: Some("1000.00".to_string()),
+            period: Period::Quarterly,
+            kind: TermKind::Ttm,
+            source: Source::Yahoo,
+            cash_flows_from_used_in_operating_activities_direct: Some(Decimal::new(100000, 2)),
+            operating_cash_flow: Some(Decimal::new(100000, 2)),
+            investing_cash_flow: Some(Decimal::new(100000, 2)),
+            financing_cash_flow: Some(Decimal::new(100000, 2)),
+            end_cash_position: Some(Decimal::new(100000, 2)),
+            capital_expenditure: Some(Decimal::new(100000, 2)),
+            issuance_of_capital_stock: Some(Decimal::new(100000, 2)),
+            issuance_of_debt: Some(Decimal::new(100000, 2)),
+            repayment_of_debt: Some(Decimal::new(100000, 2)),
+            repurchase_of_capital_stock: Some(Decimal::new(100000, 2)),
+            free_cash_flow: Some(Decimal::new(100000, 2)),
+            income_tax_paid_supplemental_data: Some(Decimal::new(100000, 2)),
+            interest_paid_supplemental_data: Some(Decimal::new(100000, 2)),
+            other_cash_adjustment_inside_change_in_cash: Some(Decimal::new(100000, 2)),
+            cash_flow_from_continuing_operating_activities: Some(Decimal::new(100000, 2)),
+            net_income_from_continuing_operations: Some(Decimal::new(100000, 2)),
+            depreciation_amortization_depletion: Some(Decimal::new(100000, 2)),
+            change_in_working_capital: Some(Decimal::new(100000, 2)),
+            cash_flow_from_continuing_investing_activities: Some(Decimal::new(100000, 2)),
+            cash_flow_from_continuing_financing_activities: Some(Decimal::new(100000, 2)),
+            cash_dividends_paid: Some(Decimal::new(100000, 2)),
+            extra: BTreeMap::from([("Unknown Row".to_string(), Some(Decimal::new(100000, 2)))]),
             filed: date,
+            filing: Some(Filing {
+                fiscal_year: Some(year),
+                fiscal_period: Some("Q1".to_string()),
+                accession: Some("0000000000-24-000001".to_string()),
+            }),
             version: 0,
         };
 
@@ -267,7 +173,7 @@ mod tests {
         CashFlowDB::save(&mut db, TABLE, cash_flow.clone()).unwrap();
 
         // Retrieved saved Cash Flow from database, by using its hash
-        let res = CashFlowDB::read(&mut db, TABLE, &hash).unwrap();
+        let res = CashFlowDB::read(&mut db, TABLE, hash).unwrap();
 
         if let Some(res) = res {
             assert_eq!(res, cash_flow.clone());
@@ -289,7 +195,7 @@ mod tests {
     }
 
     fn drop_database(client: &mut Client) {
-        let sql = format!("DROP TABLE IF EXISTS {TABLE};");
+        let sql = format!("DROP TABLE IF EXISTS {TABLE}, {TABLE}_history;");
 
         client
             .batch_execute(&sql)
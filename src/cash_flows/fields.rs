@@ -0,0 +1,293 @@
+//! Known line items of the cash flow statement
+
+use crate::statement::{FieldMeta, Sign, Unit};
+
+/// Every known cash flow statement line item, in declaration order
+pub const FIELDS: &[FieldMeta] = &[
+    FieldMeta {
+        name: "cash_flows_from_used_in_operating_activities_direct",
+        title: "Cash Flows from Used in Operating Activities Direct",
+        key: "CashFlowsfromusedinOperatingActivitiesDirect",
+        description: "Operating cash flow reported using the direct method",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "operating_cash_flow",
+        title: "Operating Cash Flow",
+        key: "OperatingCashFlow",
+        description: "Cash generated by the normal business operations",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "investing_cash_flow",
+        title: "Investing Cash Flow",
+        key: "InvestingCashFlow",
+        description: "Cash spent on or received from investments",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "financing_cash_flow",
+        title: "Financing Cash Flow",
+        key: "FinancingCashFlow",
+        description: "Cash exchanged with lenders and shareholders",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "end_cash_position",
+        title: "End Cash Position",
+        key: "EndCashPosition",
+        description: "Cash and cash equivalents at the end of the period",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "capital_expenditure",
+        title: "Capital Expenditure",
+        key: "CapitalExpenditure",
+        description: "Cash spent on property, plant and equipment",
+        unit: Unit::Currency,
+        sign: Sign::Negative,
+        subtotal: false,
+        parent: Some("investing_cash_flow"),
+    },
+    FieldMeta {
+        name: "issuance_of_capital_stock",
+        title: "Issuance of Capital Stock",
+        key: "IssuanceOfCapitalStock",
+        description: "Cash received from issuing shares",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("financing_cash_flow"),
+    },
+    FieldMeta {
+        name: "issuance_of_debt",
+        title: "Issuance of Debt",
+        key: "IssuanceOfDebt",
+        description: "Cash received from borrowing",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("financing_cash_flow"),
+    },
+    FieldMeta {
+        name: "repayment_of_debt",
+        title: "Repayment of Debt",
+        key: "RepaymentOfDebt",
+        description: "Cash spent on repaying debt",
+        unit: Unit::Currency,
+        sign: Sign::Negative,
+        subtotal: false,
+        parent: Some("financing_cash_flow"),
+    },
+    FieldMeta {
+        name: "repurchase_of_capital_stock",
+        title: "Repurchase of Capital Stock",
+        key: "RepurchaseOfCapitalStock",
+        description: "Cash spent on buying back shares",
+        unit: Unit::Currency,
+        sign: Sign::Negative,
+        subtotal: false,
+        parent: Some("financing_cash_flow"),
+    },
+    FieldMeta {
+        name: "free_cash_flow",
+        title: "Free Cash Flow",
+        key: "FreeCashFlow",
+        description: "Operating cash flow less capital expenditure",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "income_tax_paid_supplemental_data",
+        title: "Income Tax Paid Supplemental Data",
+        key: "IncomeTaxPaidSupplementalData",
+        description: "Income tax paid in cash",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "interest_paid_supplemental_data",
+        title: "Interest Paid Supplemental Data",
+        key: "InterestPaidSupplementalData",
+        description: "Interest paid in cash",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "other_cash_adjustment_inside_change_in_cash",
+        title: "Other Cash Adjustment Inside Change in Cash",
+        key: "OtherCashAdjustmentInsideChangeinCash",
+        description: "Other adjustments included in the change in cash",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "cash_flow_from_continuing_operating_activities",
+        title: "Cash Flow from Continuing Operating Activities",
+        key: "CashFlowFromContinuingOperatingActivities",
+        description: "Operating cash flow of the continuing operations",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: Some("operating_cash_flow"),
+    },
+    FieldMeta {
+        name: "net_income_from_continuing_operations",
+        title: "Net Income from Continuing Operations",
+        key: "NetIncomeFromContinuingOperations",
+        description: "Net income the operating cash flow is reconciled from",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: Some("cash_flow_from_continuing_operating_activities"),
+    },
+    FieldMeta {
+        name: "depreciation_amortization_depletion",
+        title: "Depreciation Amortization Depletion",
+        key: "DepreciationAmortizationDepletion",
+        description: "Non cash depreciation, amortization and depletion charges",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("cash_flow_from_continuing_operating_activities"),
+    },
+    FieldMeta {
+        name: "change_in_working_capital",
+        title: "Change in working capital",
+        key: "ChangeInWorkingCapital",
+        description: "Cash tied up in or released from working capital",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: Some("cash_flow_from_continuing_operating_activities"),
+    },
+    FieldMeta {
+        name: "cash_flow_from_continuing_investing_activities",
+        title: "Cash Flow from Continuing Investing Activities",
+        key: "CashFlowFromContinuingInvestingActivities",
+        description: "Investing cash flow of the continuing operations",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: Some("investing_cash_flow"),
+    },
+    FieldMeta {
+        name: "cash_flow_from_continuing_financing_activities",
+        title: "Cash Flow from Continuing Financing Activities",
+        key: "CashFlowFromContinuingFinancingActivities",
+        description: "Financing cash flow of the continuing operations",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: Some("financing_cash_flow"),
+    },
+    FieldMeta {
+        name: "cash_dividends_paid",
+        title: "Cash Dividends Paid",
+        key: "CashDividendsPaid",
+        description: "Dividends paid to shareholders",
+        unit: Unit::Currency,
+        sign: Sign::Negative,
+        subtotal: false,
+        parent: Some("cash_flow_from_continuing_financing_activities"),
+    },
+];
+
+/// Investing.com row titles and the fields they are stored in
+pub const INVESTING: &[(&str, &str)] = &[
+    ("Cash From Operating Activities", "operating_cash_flow"),
+    (
+        "Net Income/Starting Line",
+        "net_income_from_continuing_operations",
+    ),
+    (
+        "Depreciation/Depletion",
+        "depreciation_amortization_depletion",
+    ),
+    ("Changes in Working Capital", "change_in_working_capital"),
+    ("Cash From Investing Activities", "investing_cash_flow"),
+    ("Capital Expenditures", "capital_expenditure"),
+    ("Cash From Financing Activities", "financing_cash_flow"),
+    ("Total Cash Dividends Paid", "cash_dividends_paid"),
+    ("Cash Interest Paid", "interest_paid_supplemental_data"),
+    ("Cash Taxes Paid", "income_tax_paid_supplemental_data"),
+];
+
+/// US-GAAP tags and the fields they are stored in, preferred tags first
+pub const US_GAAP: &[(&str, &str)] = &[
+    (
+        "NetCashProvidedByUsedInOperatingActivities",
+        "operating_cash_flow",
+    ),
+    (
+        "NetCashProvidedByUsedInInvestingActivities",
+        "investing_cash_flow",
+    ),
+    (
+        "NetCashProvidedByUsedInFinancingActivities",
+        "financing_cash_flow",
+    ),
+    (
+        "CashCashEquivalentsRestrictedCashAndRestrictedCashEquivalents",
+        "end_cash_position",
+    ),
+    (
+        "PaymentsToAcquirePropertyPlantAndEquipment",
+        "capital_expenditure",
+    ),
+    (
+        "ProceedsFromIssuanceOfCommonStock",
+        "issuance_of_capital_stock",
+    ),
+    ("ProceedsFromIssuanceOfLongTermDebt", "issuance_of_debt"),
+    ("RepaymentsOfLongTermDebt", "repayment_of_debt"),
+    (
+        "PaymentsForRepurchaseOfCommonStock",
+        "repurchase_of_capital_stock",
+    ),
+    ("IncomeTaxesPaidNet", "income_tax_paid_supplemental_data"),
+    ("InterestPaidNet", "interest_paid_supplemental_data"),
+    (
+        "NetCashProvidedByUsedInOperatingActivitiesContinuingOperations",
+        "cash_flow_from_continuing_operating_activities",
+    ),
+    ("ProfitLoss", "net_income_from_continuing_operations"),
+    ("NetIncomeLoss", "net_income_from_continuing_operations"),
+    (
+        "DepreciationDepletionAndAmortization",
+        "depreciation_amortization_depletion",
+    ),
+    (
+        "NetCashProvidedByUsedInInvestingActivitiesContinuingOperations",
+        "cash_flow_from_continuing_investing_activities",
+    ),
+    (
+        "NetCashProvidedByUsedInFinancingActivitiesContinuingOperations",
+        "cash_flow_from_continuing_financing_activities",
+    ),
+    ("PaymentsOfDividends", "cash_dividends_paid"),
+    ("PaymentsOfDividendsCommonStock", "cash_dividends_paid"),
+];
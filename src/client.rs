@@ -0,0 +1,434 @@
+//! Reusable HTTP client used by the spiders
+
+use crate::cache::Cache;
+use crate::consent::{self, Interstitial};
+use crate::error::Error;
+use crate::fixtures::{FixtureMode, Fixtures};
+use crate::rate_limit::RateLimiter;
+use crate::retry::{self, RetryPolicy};
+#[cfg(feature = "tokio")]
+use crate::AsyncSpiderClient;
+use crate::{Period, Spider, USER_AGENT, YAHOO_ROOT};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::Proxy;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Downloads pages for the spiders, sharing one connection pool
+/// between all requests.
+#[derive(Debug, Clone)]
+pub struct SpiderClient {
+    client: reqwest::blocking::Client,
+    pipeline: Arc<Pipeline>,
+}
+
+impl SpiderClient {
+    /// Create a client with the default configuration
+    pub fn new() -> Result<Self, reqwest::Error> {
+        SpiderClient::builder().build()
+    }
+
+    /// Configure a new client
+    pub fn builder() -> SpiderClientBuilder {
+        SpiderClientBuilder::default()
+    }
+
+    /// Root url all statement pages are fetched from
+    pub fn base_url(&self) -> &str {
+        &self.pipeline.base_url
+    }
+
+    /// Download the `period` page of `S` for `symbol`
+    pub fn fetch<S: Spider>(&self, symbol: &str, period: Period) -> Result<String, Error> {
+        let url = S::url(self.base_url(), symbol, period);
+        println!("---> Fetching {} ({period}) for: {symbol}", S::NAME);
+        println!("---> {url}");
+        self.get(&url)
+    }
+
+    /// Download the body of `url`. In replay mode it is served from
+    /// the recorded fixtures, otherwise from the cache while fresh.
+    pub fn get(&self, url: &str) -> Result<String, Error> {
+        self.get_with(url, &HeaderMap::new())
+    }
+
+    /// Download the body of `url` like [`get`](Self::get), sending
+    /// `headers` along with the ones of the client
+    pub fn get_with(&self, url: &str, headers: &HeaderMap) -> Result<String, Error> {
+        match self.pipeline.lookup(url) {
+            Some(res) => res,
+            None => self.pipeline.finish(url, self.download(url, headers)),
+        }
+    }
+
+    /// Download `url`, retrying failures according to the retry policy
+    fn download(&self, url: &str, headers: &HeaderMap) -> Result<String, Error> {
+        let retry = &self.pipeline.retry;
+        let mut attempt = 0;
+
+        loop {
+            match self.try_download(url, headers) {
+                Err(err) if retry.should_retry(attempt, &err) => {
+                    thread::sleep(retry.delay(attempt, &err));
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    fn try_download(&self, url: &str, headers: &HeaderMap) -> Result<String, Error> {
+        let mut page = self.send(url, headers)?;
+
+        if let Some((action, fields)) = self.pipeline.consent_form(&page) {
+            println!("---> Answering the cookie consent form");
+            thread::sleep(self.pipeline.rate_limiter.reserve(action.as_str()));
+            let response = self.client.post(action).form(&fields).send()?;
+            retry::check_status(url, response.url(), response.status(), response.headers())?;
+            page = self.send(url, headers)?;
+        }
+
+        self.pipeline.check(url, &page)?;
+        Ok(page.body)
+    }
+
+    /// Request `url` with the extra `headers`
+    fn send(&self, url: &str, headers: &HeaderMap) -> Result<Page, Error> {
+        thread::sleep(self.pipeline.rate_limiter.reserve(url));
+        let response = self.client.get(url).headers(headers.clone()).send()?;
+        let final_url = response.url().clone();
+        retry::check_status(url, &final_url, response.status(), response.headers())?;
+        let content_type = Page::content_type(response.headers());
+
+        Ok(Page {
+            url: final_url,
+            content_type,
+            body: response.text()?,
+        })
+    }
+}
+
+/// A downloaded page
+#[derive(Debug, Clone)]
+pub(crate) struct Page {
+    /// Url the page was served from, after following redirects
+    pub(crate) url: reqwest::Url,
+    pub(crate) content_type: Option<String>,
+    pub(crate) body: String,
+}
+
+impl Page {
+    /// Content type in the response `headers`
+    pub(crate) fn content_type(headers: &HeaderMap) -> Option<String> {
+        headers
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(str::to_string)
+    }
+}
+
+/// Everything about downloading a page that does not depend on the HTTP
+/// client, shared by [`SpiderClient`] and the async client. Its file
+/// access is blocking.
+#[derive(Debug, Clone)]
+pub(crate) struct Pipeline {
+    pub(crate) base_url: String,
+    fixtures: Option<Fixtures>,
+    cache: Option<Cache>,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) rate_limiter: RateLimiter,
+    consent: bool,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Pipeline {
+            base_url: YAHOO_ROOT.to_string(),
+            fixtures: None,
+            cache: None,
+            retry: RetryPolicy::default(),
+            rate_limiter: RateLimiter::default(),
+            consent: false,
+        }
+    }
+}
+
+impl Pipeline {
+    /// Body of `url` served without downloading it, from the recorded
+    /// fixtures in replay mode or from the cache while fresh
+    pub(crate) fn lookup(&self, url: &str) -> Option<Result<String, Error>> {
+        if let Some(fixtures) = &self.fixtures {
+            if fixtures.mode() == FixtureMode::Replay {
+                return Some(fixtures.load(url));
+            }
+        }
+
+        self.cache
+            .as_ref()
+            .and_then(|cache| cache.fresh(url))
+            .map(Ok)
+    }
+
+    /// Body served for `url` once downloading it gave `downloaded`.
+    /// Failed downloads fall back to a stale cached page, successful
+    /// ones are cached and recorded.
+    pub(crate) fn finish(
+        &self,
+        url: &str,
+        downloaded: Result<String, Error>,
+    ) -> Result<String, Error> {
+        let html = match downloaded {
+            Ok(html) => html,
+            Err(err) => {
+                return self
+                    .cache
+                    .as_ref()
+                    .and_then(|cache| cache.stale(url))
+                    .ok_or(err)
+            }
+        };
+
+        if let Some(cache) = &self.cache {
+            cache.store(url, &html)?;
+        }
+
+        if let Some(fixtures) = &self.fixtures {
+            fixtures.save(url, &html)?;
+        }
+
+        Ok(html)
+    }
+
+    /// Kind of interstitial `page` is, if it is one
+    fn interstitial(&self, page: &Page) -> Option<Interstitial> {
+        consent::detect(
+            &page.url,
+            &self.base_url,
+            page.content_type.as_deref(),
+            &page.body,
+        )
+    }
+
+    /// Url and fields of the consent form to answer when `page` is a
+    /// consent page and answering them is enabled
+    pub(crate) fn consent_form(
+        &self,
+        page: &Page,
+    ) -> Option<(reqwest::Url, Vec<(String, String)>)> {
+        if self.consent && self.interstitial(page) == Some(Interstitial::Consent) {
+            consent::form(&page.url, &page.body)
+        } else {
+            None
+        }
+    }
+
+    /// Fail with [`Error::Interstitial`] if `page`, downloaded for `url`,
+    /// is an interstitial
+    pub(crate) fn check(&self, url: &str, page: &Page) -> Result<(), Error> {
+        match self.interstitial(page) {
+            Some(kind) => Err(Error::Interstitial {
+                url: url.to_string(),
+                kind,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Builder for [`SpiderClient`]
+#[derive(Debug, Clone)]
+pub struct SpiderClientBuilder {
+    user_agent: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    headers: HeaderMap,
+    pipeline: Pipeline,
+}
+
+impl Default for SpiderClientBuilder {
+    fn default() -> Self {
+        SpiderClientBuilder {
+            user_agent: USER_AGENT.to_string(),
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            headers: HeaderMap::new(),
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl SpiderClientBuilder {
+    /// User agent sent with every request, defaults to [`USER_AGENT`]
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Root url to fetch pages from, defaults to [`YAHOO_ROOT`]
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.pipeline.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Total time allowed for a single request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Time allowed for establishing a connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Route every request through `proxy`
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Extra header sent with every request
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Save every downloaded page to `dir`
+    pub fn record(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.pipeline.fixtures = Some(Fixtures::new(dir, FixtureMode::Record));
+        self
+    }
+
+    /// Serve pages recorded in `dir` instead of downloading them
+    pub fn replay(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.pipeline.fixtures = Some(Fixtures::new(dir, FixtureMode::Replay));
+        self
+    }
+
+    /// Cache downloaded pages on disk
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.pipeline.cache = Some(cache);
+        self
+    }
+
+    /// How to retry failed downloads, defaults to
+    /// [`RetryPolicy::default`]
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.pipeline.retry = retry;
+        self
+    }
+
+    /// Throttle downloads with `rate_limiter`. By default all clients
+    /// share one limiter, see [`RateLimiter::default`].
+    pub fn rate_limit(mut self, rate_limiter: RateLimiter) -> Self {
+        self.pipeline.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Answer cookie consent pages, declining optional cookies, and keep
+    /// the cookies they set for later requests. Otherwise these pages
+    /// fail with [`Error::Interstitial`].
+    pub fn consent(mut self, consent: bool) -> Self {
+        self.pipeline.consent = consent;
+        self
+    }
+
+    /// Build the client
+    pub fn build(self) -> Result<SpiderClient, reqwest::Error> {
+        let mut builder = reqwest::blocking::Client::builder()
+            .user_agent(self.user_agent)
+            .default_headers(self.headers)
+            .cookie_store(self.pipeline.consent);
+
+        // Otherwise the blocking client's default of 30 seconds applies
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        if let Some(proxy) = self.proxy {
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(SpiderClient {
+            client: builder.build()?,
+            pipeline: Arc::new(self.pipeline),
+        })
+    }
+
+    /// Build an async client with the same configuration
+    #[cfg(feature = "tokio")]
+    pub fn build_async(self) -> Result<AsyncSpiderClient, reqwest::Error> {
+        let mut builder = reqwest::Client::builder()
+            .user_agent(self.user_agent)
+            .default_headers(self.headers)
+            .cookie_store(self.pipeline.consent);
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        if let Some(proxy) = self.proxy {
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(AsyncSpiderClient::from_parts(
+            builder.build()?,
+            Arc::new(self.pipeline),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+
+    struct Page;
+    impl Spider for Page {
+        const NAME: &'static str = "Test Page";
+
+        fn url(root: &str, symbol: &str, period: Period) -> String {
+            format!("{root}/quote/{symbol}/page{}", period.query())
+        }
+    }
+
+    #[test]
+    fn test_spider_client_configuration() {
+        let server = MockServer::start(vec![MockResponse::ok("<html></html>")]);
+        let root = format!("{}/", server.url);
+
+        let client = SpiderClient::builder()
+            .base_url(&root)
+            .user_agent("finspider-test")
+            .timeout(Duration::from_secs(5))
+            .header(
+                HeaderName::from_static("x-finspider"),
+                HeaderValue::from_static("yes"),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(client.base_url(), server.url);
+
+        let html = client.fetch::<Page>("SBKP.JO", Period::Annual).unwrap();
+        let head = &server.requests()[0];
+
+        assert_eq!(html, "<html></html>");
+        assert_eq!(head[0], "GET /quote/SBKP.JO/page HTTP/1.1");
+        assert!(head.contains(&"user-agent: finspider-test".to_string()));
+        assert!(head.contains(&"x-finspider: yes".to_string()));
+    }
+}
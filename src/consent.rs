@@ -0,0 +1,243 @@
+//! Detection of cookie consent and bot check pages
+//!
+//! From some regions and networks Yahoo answers with an interstitial
+//! page instead of the requested one, usually with a successful status.
+//! These pages are recognised so they are reported as
+//! [`Error::Interstitial`](crate::error::Error::Interstitial) rather than
+//! parsed into an empty list of statements. The consent form can also be
+//! submitted, after which the cookies it sets give access to the pages.
+
+use scraper::{Html, Selector};
+use std::fmt;
+
+/// Kind of page shown instead of the requested one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interstitial {
+    /// Cookie consent form
+    Consent,
+    /// Captcha or "unusual traffic" check
+    BotCheck,
+}
+
+impl fmt::Display for Interstitial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Consent => write!(f, "cookie consent page"),
+            Self::BotCheck => write!(f, "bot check page"),
+        }
+    }
+}
+
+/// Kind of interstitial `body`, downloaded from `url` after following
+/// redirects, if it is one. Only HTML pages from Yahoo are checked, served
+/// by a `yahoo.com` host or from `root`, the root url of the pages.
+pub fn detect(
+    url: &reqwest::Url,
+    root: &str,
+    content_type: Option<&str>,
+    body: &str,
+) -> Option<Interstitial> {
+    if !is_yahoo(url, root) || !is_html(content_type) {
+        return None;
+    }
+
+    let host = url.host_str().unwrap_or_default();
+    if host.starts_with("consent.") || host.starts_with("guce.") {
+        return Some(Interstitial::Consent);
+    }
+
+    let consent = body.contains("consent-form") || body.contains("collectConsent");
+    let lowercase = body.to_lowercase();
+    let bot_check = [
+        "unusual traffic",
+        "are you a robot",
+        "g-recaptcha",
+        "h-captcha",
+    ]
+    .iter()
+    .any(|marker| lowercase.contains(marker));
+
+    // Statement pages may embed the consent manager or mention the
+    // markers, but an interstitial has no statement table
+    if !(consent || bot_check) || has_statement(body) {
+        None
+    } else if consent {
+        Some(Interstitial::Consent)
+    } else {
+        Some(Interstitial::BotCheck)
+    }
+}
+
+/// Whether `url` is served by Yahoo or from `root`
+fn is_yahoo(url: &reqwest::Url, root: &str) -> bool {
+    let host = url.host_str().unwrap_or_default();
+
+    host == "yahoo.com"
+        || host.ends_with(".yahoo.com")
+        || reqwest::Url::parse(root).is_ok_and(|root| root.origin() == url.origin())
+}
+
+/// Whether `content_type` is that of an HTML page
+fn is_html(content_type: Option<&str>) -> bool {
+    content_type.is_some_and(|content_type| {
+        content_type
+            .trim_start()
+            .to_ascii_lowercase()
+            .starts_with("text/html")
+    })
+}
+
+/// Whether `body` holds the table of a statement page
+fn has_statement(body: &str) -> bool {
+    Selector::parse(".tableBody .row")
+        .is_ok_and(|rows| Html::parse_document(body).select(&rows).next().is_some())
+}
+
+/// Url and fields to post to decline all optional cookies on the
+/// consent page `body`, downloaded from `url`
+pub fn form(url: &reqwest::Url, body: &str) -> Option<(reqwest::Url, Vec<(String, String)>)> {
+    let document = Html::parse_document(body);
+    let forms = Selector::parse("form").ok()?;
+    let inputs = Selector::parse("input[type=hidden]").ok()?;
+    let buttons = Selector::parse("button[type=submit][name]").ok()?;
+
+    let form = document
+        .select(&forms)
+        .find(|form| form.select(&buttons).next().is_some())?;
+    let action = url
+        .join(form.value().attr("action").unwrap_or_default())
+        .ok()?;
+
+    let mut fields: Vec<(String, String)> = form
+        .select(&inputs)
+        .filter_map(|input| {
+            let name = input.value().attr("name")?;
+            Some((name.to_string(), input.value().attr("value")?.to_string()))
+        })
+        .collect();
+
+    // Prefer rejecting, which gives access all the same
+    let submit: Vec<_> = form.select(&buttons).collect();
+    let button = submit
+        .iter()
+        .find(|button| button.value().attr("name") == Some("reject"))
+        .or(submit.first())?;
+    fields.push((
+        button.value().attr("name")?.to_string(),
+        button.value().attr("value").unwrap_or_default().to_string(),
+    ));
+
+    Some((action, fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::rate_limit::RateLimiter;
+    use crate::retry::RetryPolicy;
+    use crate::{SpiderClient, YAHOO_ROOT};
+    use std::fs;
+
+    const CONSENT: &str = r#"<html><body>
+<form method="post" class="consent-form" action="/v2/collectConsent?sessionId=3_cc-session">
+<input type="hidden" name="csrfToken" value="token">
+<input type="hidden" name="sessionId" value="3_cc-session">
+<input type="text" name="ignored" value="text">
+<button type="submit" class="btn accept-all" name="agree" value="agree">Accept all</button>
+<button type="submit" class="btn reject-all" name="reject" value="reject">Reject all</button>
+</form>
+</body></html>"#;
+
+    #[test]
+    fn test_consent() {
+        let url = reqwest::Url::parse("https://consent.yahoo.com/v2/collectConsent").unwrap();
+        let page = reqwest::Url::parse("https://finance.yahoo.com/quote/AAPL").unwrap();
+
+        let html = Some("text/html; charset=utf-8");
+        let detect = |url, content_type, body| detect(url, YAHOO_ROOT, content_type, body);
+
+        assert_eq!(detect(&url, html, ""), Some(Interstitial::Consent));
+        assert_eq!(detect(&page, html, CONSENT), Some(Interstitial::Consent));
+        assert_eq!(
+            detect(
+                &page,
+                html,
+                "<p>Our systems have detected Unusual Traffic</p>"
+            ),
+            Some(Interstitial::BotCheck)
+        );
+        assert_eq!(detect(&page, html, "<html></html>"), None);
+
+        // Statement pages and other responses mentioning the markers
+        let statement = r#"<div class="tableBody"><div class="row">Unusual Traffic</div></div>"#;
+        assert_eq!(detect(&page, html, statement), None);
+        assert_eq!(detect(&page, Some("application/json"), CONSENT), None);
+        assert_eq!(detect(&page, None, CONSENT), None);
+        let fixture = fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/yahoo/quote_SBKP.JO_balance-sheet.html"
+        ))
+        .unwrap();
+        let embedded = fixture.replace(
+            "</body>",
+            r#"<script src="https://consent.cmp.oath.com/cmp.js"></script>
+<script>window.consentManager = { form: "consent-form", action: "/v2/collectConsent" };</script>
+</body>"#,
+        );
+        assert!(embedded.contains("collectConsent"));
+        assert_eq!(detect(&page, html, &embedded), None);
+        let sec = reqwest::Url::parse("https://www.sec.gov/consent-form").unwrap();
+        assert_eq!(detect(&sec, html, CONSENT), None);
+
+        let (action, fields) = form(&url, CONSENT).unwrap();
+        assert_eq!(
+            action.as_str(),
+            "https://consent.yahoo.com/v2/collectConsent?sessionId=3_cc-session"
+        );
+        assert_eq!(fields[0], ("csrfToken".to_string(), "token".to_string()));
+        assert_eq!(fields[2], ("reject".to_string(), "reject".to_string()));
+        assert_eq!(fields.len(), 3);
+
+        // Reported without the consent flow
+        let server = MockServer::start(vec![
+            MockResponse::status(302, "").header("Location", "/consent"),
+            MockResponse::ok(CONSENT).header("Content-Type", "text/html"),
+        ]);
+        let builder = SpiderClient::builder()
+            .base_url(&server.url)
+            .retry(RetryPolicy::none())
+            .rate_limit(RateLimiter::unlimited());
+        let client = builder.clone().build().unwrap();
+        let url = format!("{}/quote/SBKP.JO/balance-sheet", server.url);
+
+        assert!(matches!(
+            client.get(&url),
+            Err(Error::Interstitial {
+                kind: Interstitial::Consent,
+                ..
+            })
+        ));
+        server.requests();
+
+        // Completed with it, the cookie is sent with the retried request
+        let server = MockServer::start(vec![
+            MockResponse::ok(CONSENT).header("Content-Type", "text/html"),
+            MockResponse::ok("").header("Set-Cookie", "A1=consented; Path=/"),
+            MockResponse::ok("<html></html>"),
+        ]);
+        let client = builder.base_url(&server.url).consent(true).build().unwrap();
+        let url = format!("{}/quote/SBKP.JO/balance-sheet", server.url);
+
+        assert_eq!(client.get(&url).unwrap(), "<html></html>");
+        let requests = server.requests();
+        assert_eq!(
+            requests[1][0],
+            "POST /v2/collectConsent?sessionId=3_cc-session HTTP/1.1"
+        );
+        assert!(requests[1]
+            .contains(&"csrfToken=token&sessionId=3_cc-session&reject=reject".to_string()));
+        assert!(requests[2].contains(&"cookie: A1=consented".to_string()));
+    }
+}
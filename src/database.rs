@@ -0,0 +1,298 @@
+//! PostgreSQL storage shared by all statement types
+//!
+//! Restated statements replace the stored version of their term, which
+//! moves to `{table}_history` with the time it stopped being current.
+//! Together the two tables tell which statements were known at any time.
+//! Only one version of a term is current, so restatements can't be saved
+//! next to it and go through [`revise`].
+
+use crate::error::Error;
+use crate::migrations;
+use crate::statement;
+use crate::store::{OnConflict, Saved};
+use crate::{Decimal, FinancialStatement};
+use chrono::{DateTime, NaiveDate, Utc};
+use postgres::types::{Json, ToSql};
+use postgres::{Client, GenericClient, Row};
+
+/// Create the table of `S` called `table`, owned by `owner`, migrating
+/// tables created by older versions
+pub(crate) fn init<S: FinancialStatement>(
+    client: &mut Client,
+    table: &str,
+    owner: &str,
+) -> Result<(), Error> {
+    migrations::migrate::<S>(client, table, owner)
+}
+
+/// Insert `statement` into `table`, failing if a version of its term is
+/// stored. Restatements go through [`revise`].
+pub(crate) fn save<S: FinancialStatement>(
+    client: &mut Client,
+    table: &str,
+    statement: &S,
+) -> Result<(), Error> {
+    insert(client, table, statement, "")?;
+    Ok(())
+}
+
+/// Insert `statement` into `table`, doing `on_conflict` when one with
+/// the same hash is stored. Statements with the same hash have the same
+/// values, so only the filing and version are updated. Fails if another
+/// version of its term is stored, like [`save`].
+pub(crate) fn upsert<S: FinancialStatement>(
+    client: &mut Client,
+    table: &str,
+    statement: &S,
+    on_conflict: OnConflict,
+) -> Result<Saved, Error> {
+    if insert(client, table, statement, " ON CONFLICT (hash) DO NOTHING")? > 0 {
+        return Ok(Saved::Inserted);
+    }
+    if on_conflict == OnConflict::Skip {
+        return Ok(Saved::Unchanged);
+    }
+
+    let sql = format!(
+        "UPDATE {table} SET filed = $1, filing = $2, version = $3
+             WHERE hash = $4 AND (filed, filing, version) IS DISTINCT FROM ($1, $2, $3);"
+    );
+    let updated = client
+        .execute(
+            &sql,
+            &[
+                &statement.filed(),
+                &statement.filing().map(Json),
+                &statement.version(),
+                &statement.hash(),
+            ],
+        )
+        .map_err(|_| Error::SQL)?;
+
+    Ok(if updated > 0 {
+        Saved::Updated
+    } else {
+        Saved::Unchanged
+    })
+}
+
+/// Insert `statement` into `table`, followed by the `conflict` clause
+fn insert<S: FinancialStatement>(
+    client: &mut impl GenericClient,
+    table: &str,
+    statement: &S,
+    conflict: &str,
+) -> Result<u64, Error> {
+    let names = statement::columns::<S>();
+    let placeholders: Vec<String> = (1..=names.len()).map(|n| format!("${n}")).collect();
+    let sql = format!(
+        "INSERT INTO {table} ({}) VALUES ({}){conflict};",
+        names.join(", "),
+        placeholders.join(", ")
+    );
+
+    let (symbol, term) = (statement.symbol(), statement.term());
+    let (period, kind, source) = (
+        statement.period().as_str(),
+        statement.kind().as_str(),
+        statement.source().as_str(),
+    );
+    let fields = statement.fields();
+    let extra = Json(statement.extra());
+    let (filed, filing) = (statement.filed(), statement.filing().map(Json));
+    let (hash, version) = (statement.hash(), statement.version());
+
+    let mut params: Vec<&(dyn ToSql + Sync)> = vec![&symbol, &term, &period, &kind, &source];
+    params.extend(fields.iter().map(|(_, value)| value as &(dyn ToSql + Sync)));
+    params.extend([
+        &extra as &(dyn ToSql + Sync),
+        &filed,
+        &filing,
+        &hash,
+        &version,
+    ]);
+
+    client.execute(&sql, &params).map_err(|_| Error::SQL)
+}
+
+/// Statement of `table` identified by `hash`
+pub(crate) fn read<S: FinancialStatement>(
+    client: &mut Client,
+    table: &str,
+    hash: &str,
+) -> Result<Option<S>, Error> {
+    let sql = format!("SELECT * FROM {table} WHERE hash = $1");
+    Ok(query(client, &sql, &[&hash])?.into_iter().next())
+}
+
+/// Every statement of `table`
+pub(crate) fn read_all<S: FinancialStatement>(
+    client: &mut Client,
+    table: &str,
+) -> Result<Vec<S>, Error> {
+    let sql = format!("SELECT * FROM {table}");
+    query(client, &sql, &[])
+}
+
+/// Every statement of `table` for `symbol`
+pub(crate) fn read_all_by_symbol<S: FinancialStatement>(
+    client: &mut Client,
+    table: &str,
+    symbol: &str,
+) -> Result<Vec<S>, Error> {
+    let sql = format!("SELECT * FROM {table} WHERE symbol = $1");
+    query(client, &sql, &[&symbol])
+}
+
+/// Statements of `table` for `symbol` with a term from `from` to `to`,
+/// oldest first
+pub(crate) fn read_range<S: FinancialStatement>(
+    client: &mut Client,
+    table: &str,
+    symbol: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<S>, Error> {
+    let sql =
+        format!("SELECT * FROM {table} WHERE symbol = $1 AND term BETWEEN $2 AND $3 ORDER BY term");
+    query(client, &sql, &[&symbol, &from, &to])
+}
+
+/// Delete the statement of `table` identified by `hash`, returning
+/// whether it was stored
+pub(crate) fn delete(client: &mut Client, table: &str, hash: &str) -> Result<bool, Error> {
+    let sql = format!("DELETE FROM {table} WHERE hash = $1");
+    let deleted = client.execute(&sql, &[&hash]).map_err(|_| Error::SQL)?;
+
+    Ok(deleted > 0)
+}
+
+/// Make `statement` the current version of its term from `at`. Other
+/// versions of the term, from the same source and of the same kind, move
+/// to the history.
+pub(crate) fn revise<S: FinancialStatement>(
+    client: &mut Client,
+    table_name: &str,
+    statement: &S,
+    at: DateTime<Utc>,
+) -> Result<Saved, Error> {
+    let mut transaction = client.transaction().map_err(|_| Error::Transaction)?;
+    let hash = statement.hash();
+
+    let sql = format!("SELECT 1 FROM {table_name} WHERE hash = $1");
+    let stored = transaction.query(&sql, &[&hash]).map_err(|_| Error::SQL)?;
+    if !stored.is_empty() {
+        return Ok(Saved::Unchanged);
+    }
+
+    let columns = statement::columns::<S>().join(", ");
+    let same_term = "symbol = $1 AND term = $2 AND period = $3 AND kind = $4 AND source = $5";
+    let sql = format!(
+        "INSERT INTO {table_name}_history (id, {columns}, valid_from, valid_to)
+                 SELECT id, {columns}, valid_from, $6 FROM {table_name} WHERE {same_term};"
+    );
+    let replaced = transaction
+        .execute(
+            &sql,
+            &[
+                &statement.symbol(),
+                &statement.term(),
+                &statement.period().as_str(),
+                &statement.kind().as_str(),
+                &statement.source().as_str(),
+                &at,
+            ],
+        )
+        .map_err(|_| Error::SQL)?;
+
+    let sql = format!("DELETE FROM {table_name} WHERE {same_term};");
+    transaction
+        .execute(
+            &sql,
+            &[
+                &statement.symbol(),
+                &statement.term(),
+                &statement.period().as_str(),
+                &statement.kind().as_str(),
+                &statement.source().as_str(),
+            ],
+        )
+        .map_err(|_| Error::SQL)?;
+
+    insert(&mut transaction, table_name, statement, "")?;
+    let sql = format!("UPDATE {table_name} SET valid_from = $1 WHERE hash = $2;");
+    transaction
+        .execute(&sql, &[&at, &hash])
+        .map_err(|_| Error::SQL)?;
+
+    transaction.commit().map_err(|_| Error::Transaction)?;
+
+    Ok(if replaced > 0 {
+        Saved::Restated
+    } else {
+        Saved::Inserted
+    })
+}
+
+/// Statements of `symbol` with a term from `from` to `to` as they were
+/// stored at `at`, oldest first
+pub(crate) fn read_as_of<S: FinancialStatement>(
+    client: &mut Client,
+    table_name: &str,
+    symbol: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    at: DateTime<Utc>,
+) -> Result<Vec<S>, Error> {
+    let columns = statement::columns::<S>().join(", ");
+    let sql = format!(
+        "SELECT {columns} FROM {table_name}
+                 WHERE symbol = $1 AND term BETWEEN $2 AND $3 AND valid_from <= $4
+             UNION ALL
+             SELECT {columns} FROM {table_name}_history
+                 WHERE symbol = $1 AND term BETWEEN $2 AND $3 AND valid_from <= $4 AND valid_to > $4
+             ORDER BY term"
+    );
+    query(client, &sql, &[&symbol, &from, &to, &at])
+}
+
+fn query<S: FinancialStatement>(
+    client: &mut Client,
+    sql: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<Vec<S>, Error> {
+    let rows = client.query(sql, params).map_err(|_| Error::SQL)?;
+
+    rows.iter().map(statement).collect()
+}
+
+/// Statement stored in `row`
+pub(crate) fn statement<S: FinancialStatement>(row: &Row) -> Result<S, Error> {
+    let text = |name: &str| row.try_get::<_, &str>(name).map_err(|_| Error::SQL);
+
+    let mut statement = S::new(
+        text("symbol")?,
+        row.try_get("term").map_err(|_| Error::SQL)?,
+        text("period")?.parse()?,
+        text("kind")?.parse()?,
+        text("source")?.parse()?,
+    );
+
+    for field in S::FIELDS {
+        let value: Option<Decimal> = row.try_get(field.name).map_err(|_| Error::SQL)?;
+        statement.insert(field.name, value);
+    }
+
+    *statement.extra_mut() = row
+        .try_get::<_, Json<_>>("extra")
+        .map_err(|_| Error::SQL)?
+        .0;
+    *statement.filed_mut() = row.try_get("filed").map_err(|_| Error::SQL)?;
+    *statement.filing_mut() = row
+        .try_get::<_, Option<Json<_>>>("filing")
+        .map_err(|_| Error::SQL)?
+        .map(|filing| filing.0);
+    *statement.version_mut() = row.try_get("version").map_err(|_| Error::SQL)?;
+
+    Ok(statement)
+}
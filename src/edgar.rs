@@ -0,0 +1,377 @@
+//! Statements from SEC EDGAR XBRL company facts
+//!
+//! The `companyfacts` API returns every value a company has reported in
+//! its filings, keyed by US-GAAP tag. Values are read from a downloaded
+//! response or a local copy, such as one from SEC's bulk download. SEC
+//! asks for a user agent with contact details, which can be set with
+//! [`SpiderClientBuilder::user_agent`](crate::SpiderClientBuilder::user_agent).
+
+use crate::balance_sheets::{self, BalanceSheet};
+use crate::cash_flows::{self, CashFlow};
+use crate::error::{Error, ParseError};
+use crate::income_statements::{self, IncomeStatement};
+use crate::provider::Provider;
+use crate::statement::{Filing, FinancialStatement, Sign, StatementType, Unit};
+use crate::{value, Decimal, Period, Source, SpiderClient, TermKind, EDGAR_ROOT};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Forms with the statements of a fiscal year or quarter
+const FORMS: [&str; 4] = ["10-K", "10-K/A", "10-Q", "10-Q/A"];
+
+#[derive(Deserialize, Debug)]
+struct CompanyFacts {
+    facts: Facts,
+}
+
+#[derive(Deserialize, Debug)]
+struct Facts {
+    #[serde(rename = "us-gaap", default)]
+    us_gaap: HashMap<String, Concept>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Concept {
+    units: HashMap<String, Vec<Fact>>,
+}
+
+/// Value reported in a filing
+#[derive(Deserialize, Debug)]
+struct Fact {
+    /// First day covered, missing for values at a point in time
+    start: Option<NaiveDate>,
+    end: NaiveDate,
+    val: serde_json::Number,
+    /// Fiscal year of the filing
+    fy: Option<i32>,
+    /// Fiscal period of the filing, `FY` or `Q1` to `Q4`
+    fp: Option<String>,
+    /// Accession number of the filing
+    accn: Option<String>,
+    form: String,
+    filed: NaiveDate,
+}
+
+impl Fact {
+    /// Whether the fact covers a term of `period`. Quarterly filings
+    /// also report year to date values, which are left out.
+    fn is_term(&self, period: Period) -> bool {
+        let (fiscal, days) = match period {
+            Period::Annual => (self.fp.as_deref() == Some("FY"), 350..=380),
+            Period::Quarterly => (
+                self.fp.as_deref().is_some_and(|fp| fp.starts_with('Q')),
+                80..=100,
+            ),
+        };
+
+        FORMS.contains(&self.form.as_str())
+            && fiscal
+            && self
+                .start
+                .is_none_or(|start| days.contains(&(self.end - start).num_days()))
+    }
+
+    fn filing(&self) -> Filing {
+        Filing {
+            fiscal_year: self.fy,
+            fiscal_period: self.fp.clone(),
+            accession: self.accn.clone(),
+        }
+    }
+}
+
+/// US-GAAP tags and the fields they are stored in. Fields with several
+/// tags take the first tag in this list that has a value.
+pub fn tags(statement: StatementType) -> &'static [(&'static str, &'static str)] {
+    match statement {
+        StatementType::BalanceSheet => balance_sheets::fields::US_GAAP,
+        StatementType::CashFlow => cash_flows::fields::US_GAAP,
+        StatementType::IncomeStatement => income_statements::fields::US_GAAP,
+    }
+}
+
+/// Url of the company facts of the company with the central index key `cik`
+pub fn url(root: &str, cik: u64) -> String {
+    format!("{root}/api/xbrl/companyfacts/CIK{cik:010}.json")
+}
+
+/// Parse the `period` statements of `S` in company facts `json`, newest
+/// first.
+///
+/// Amounts and share counts are converted to thousands, the unit of
+/// Yahoo's statement pages, and payments are negative like on Yahoo.
+/// Terms reported by several filings keep the value first filed, and
+/// every statement is dated with the earliest filing it was read from,
+/// which is kept as its [`Filing`].
+pub fn parse<S: FinancialStatement>(
+    json: &str,
+    symbol: &str,
+    period: Period,
+) -> Result<Vec<S>, ParseError> {
+    let facts: CompanyFacts =
+        serde_json::from_str(json).map_err(|err| ParseError::Json(err.to_string()))?;
+    let mut terms: BTreeMap<NaiveDate, S> = BTreeMap::new();
+
+    for (tag, name) in tags(S::TYPE) {
+        let (Some(field), Some(concept)) = (S::field_meta(name), facts.facts.us_gaap.get(*tag))
+        else {
+            continue;
+        };
+        let unit = match field.unit {
+            Unit::Currency => "USD",
+            Unit::Shares => "shares",
+            Unit::PerShare => "USD/shares",
+            Unit::Ratio => "pure",
+        };
+
+        // Later filings repeat earlier terms, keep the first filed value
+        let mut first: BTreeMap<NaiveDate, &Fact> = BTreeMap::new();
+        for fact in concept.units.get(unit).into_iter().flatten() {
+            if !fact.is_term(period) {
+                continue;
+            }
+            match first.entry(fact.end) {
+                Entry::Occupied(mut entry) if entry.get().filed > fact.filed => {
+                    entry.insert(fact);
+                }
+                Entry::Occupied(_) => {}
+                Entry::Vacant(entry) => {
+                    entry.insert(fact);
+                }
+            }
+        }
+
+        for (end, fact) in first {
+            let statement = terms
+                .entry(end)
+                .or_insert_with(|| S::new(symbol, end, period, TermKind::Reported, Source::Edgar));
+
+            // Taken from a tag listed earlier
+            if statement.field(field.name).is_some() {
+                continue;
+            }
+
+            let err = || ParseError::Value {
+                title: tag.to_string(),
+                term: end.to_string(),
+                text: fact.val.to_string(),
+            };
+            let mut value = value::parse(&fact.val.to_string())
+                .map_err(|_| err())?
+                .ok_or_else(err)?;
+
+            if matches!(field.unit, Unit::Currency | Unit::Shares) {
+                value /= Decimal::ONE_THOUSAND;
+            }
+            if field.sign == Sign::Negative {
+                value = -value.abs();
+            }
+
+            statement.insert(field.name, Some(value.normalize()));
+            if statement.filing().is_none() || fact.filed < statement.filed() {
+                *statement.filed_mut() = fact.filed;
+                *statement.filing_mut() = Some(fact.filing());
+            }
+        }
+    }
+
+    Ok(terms.into_values().rev().collect())
+}
+
+/// Statements from SEC EDGAR, for companies filing with the SEC
+#[derive(Debug, Clone)]
+pub struct EdgarProvider {
+    client: SpiderClient,
+    base_url: String,
+    ciks: HashMap<String, u64>,
+}
+
+impl EdgarProvider {
+    pub fn new(client: SpiderClient) -> Self {
+        EdgarProvider {
+            client,
+            base_url: EDGAR_ROOT.to_string(),
+            ciks: HashMap::new(),
+        }
+    }
+
+    /// Root url of the EDGAR API, defaults to [`EDGAR_ROOT`]
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Central index key of `symbol`. Symbols without one must be the
+    /// key itself.
+    pub fn cik(mut self, symbol: &str, cik: u64) -> Self {
+        self.ciks.insert(symbol.to_string(), cik);
+        self
+    }
+
+    /// Download and parse the `period` statements of `S` for `symbol`
+    pub fn fetch<S: FinancialStatement>(
+        &self,
+        symbol: &str,
+        period: Period,
+    ) -> Result<Vec<S>, Error> {
+        let cik = match self.ciks.get(symbol) {
+            Some(cik) => *cik,
+            None => symbol
+                .parse()
+                .map_err(|_| Error::NotFound(symbol.to_string()))?,
+        };
+        let url = url(&self.base_url, cik);
+        println!("---> Fetching EDGAR {} ({period}) for: {symbol}", S::NAME);
+        println!("---> {url}");
+        let json = self.client.get(&url)?;
+        Ok(parse(&json, symbol, period)?)
+    }
+}
+
+impl Provider for EdgarProvider {
+    fn source(&self) -> Source {
+        Source::Edgar
+    }
+
+    fn balance_sheets(&self, symbol: &str, period: Period) -> Result<Vec<BalanceSheet>, Error> {
+        self.fetch(symbol, period)
+    }
+
+    fn cash_flows(&self, symbol: &str, period: Period) -> Result<Vec<CashFlow>, Error> {
+        self.fetch(symbol, period)
+    }
+
+    fn income_statements(
+        &self,
+        symbol: &str,
+        period: Period,
+    ) -> Result<Vec<IncomeStatement>, Error> {
+        self.fetch(symbol, period)
+    }
+}
+
+/// Parse the `period` statements of `S` in a company facts file
+pub fn load<S: FinancialStatement>(
+    path: impl AsRef<Path>,
+    symbol: &str,
+    period: Period,
+) -> Result<Vec<S>, Error> {
+    let json = fs::read_to_string(path)?;
+    Ok(parse(&json, symbol, period)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::rate_limit::RateLimiter;
+
+    const FIXTURE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/edgar/CIK0000320193.json"
+    );
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_edgar() {
+        let symbol = "AAPL";
+
+        let balance_sheets: Vec<BalanceSheet> = load(FIXTURE, symbol, Period::Annual).unwrap();
+        assert_eq!(balance_sheets.len(), 3);
+        assert_eq!(balance_sheets[0].source, Source::Edgar);
+        assert_eq!(balance_sheets[0].term, date(2023, 9, 30));
+        assert_eq!(balance_sheets[0].filed, date(2023, 11, 3));
+        assert_eq!(
+            balance_sheets[0].total_assets,
+            Some(Decimal::new(352583000, 0))
+        );
+        assert_eq!(
+            balance_sheets[0].ordinary_shares_number,
+            Some(Decimal::new(15550061, 0))
+        );
+
+        assert_eq!(
+            balance_sheets[0].filing,
+            Some(Filing {
+                fiscal_year: Some(2023),
+                fiscal_period: Some("FY".to_string()),
+                accession: Some("0000320193-23-000106".to_string()),
+            })
+        );
+
+        // Comparatives keep the filing that first reported them
+        assert_eq!(balance_sheets[1].filed, date(2022, 10, 28));
+        assert_eq!(
+            balance_sheets[2]
+                .filing
+                .as_ref()
+                .and_then(|filing| filing.accession.as_deref()),
+            Some("0000320193-21-000105")
+        );
+        assert_eq!(balance_sheets[2].filed, date(2021, 10, 29));
+        assert_eq!(balance_sheets[2].stockholders_equity, None);
+
+        // Payments are negative, year to date values are left out
+        let cash_flows: Vec<CashFlow> = load(FIXTURE, symbol, Period::Annual).unwrap();
+        assert_eq!(
+            cash_flows[0].capital_expenditure,
+            Some(Decimal::new(-10959000, 0))
+        );
+        assert_eq!(
+            cash_flows[0].cash_dividends_paid,
+            Some(Decimal::new(-15025000, 0))
+        );
+        let cash_flows: Vec<CashFlow> = load(FIXTURE, symbol, Period::Quarterly).unwrap();
+        assert_eq!(cash_flows.len(), 1);
+        assert_eq!(cash_flows[0].term, date(2023, 12, 30));
+
+        let income_statements: Vec<IncomeStatement> =
+            load(FIXTURE, symbol, Period::Annual).unwrap();
+        assert_eq!(income_statements.len(), 3);
+        assert_eq!(
+            income_statements[0].total_revenue,
+            Some(Decimal::new(383285000, 0))
+        );
+        assert_eq!(income_statements[0].diluted_eps, Some(Decimal::new(613, 2)));
+        assert_eq!(
+            income_statements[2].total_revenue,
+            Some(Decimal::new(42905000, 0))
+        );
+
+        // Forms other than 10-K and 10-Q are left out
+        let income_statements: Vec<IncomeStatement> =
+            load(FIXTURE, symbol, Period::Quarterly).unwrap();
+        assert_eq!(income_statements.len(), 1);
+
+        assert!(matches!(
+            parse::<BalanceSheet>("{}", symbol, Period::Annual),
+            Err(ParseError::Json(_))
+        ));
+
+        // Downloaded from a configurable root
+        let server = MockServer::start(vec![MockResponse::ok(
+            &fs::read_to_string(FIXTURE).unwrap(),
+        )]);
+        let provider = EdgarProvider::new(
+            SpiderClient::builder()
+                .rate_limit(RateLimiter::unlimited())
+                .build()
+                .unwrap(),
+        )
+        .base_url(&server.url)
+        .cik(symbol, 320193);
+        let downloaded = provider.balance_sheets(symbol, Period::Annual).unwrap();
+        assert_eq!(downloaded[0].hash(), balance_sheets[0].hash());
+        assert_eq!(
+            server.requests()[0][0],
+            "GET /api/xbrl/companyfacts/CIK0000320193.json HTTP/1.1"
+        );
+    }
+}
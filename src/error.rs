@@ -1,6 +1,9 @@
-//! database errors
+//! fetch, parse and database errors
 
+use crate::consent::Interstitial;
 use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// Errors
 #[derive(Debug)]
@@ -15,9 +18,73 @@ pub enum Error {
     SQL,
     /// Database field refferencing error
     Field,
+    /// Migrating `table` to `version` failed
+    Migration { table: String, version: i16 },
+    /// Page download error
+    Fetch(reqwest::Error),
+    /// Page parsing error
+    Parse(ParseError),
+    /// No recorded page at the given path
+    Fixture(PathBuf),
+    /// File system error
+    Io(std::io::Error),
+    /// The page (usually the symbol) does not exist
+    NotFound(String),
+    /// The provider has no id for the symbol to fetch it by
+    UnknownSymbol(String),
+    /// Too many requests, the server asked to wait `retry_after`
+    RateLimited {
+        url: String,
+        retry_after: Option<Duration>,
+    },
+    /// Access denied
+    Blocked(String),
+    /// A consent or bot check page was served instead of `url`
+    Interstitial { url: String, kind: Interstitial },
+    /// Any other unsuccessful HTTP status
+    Status { url: String, status: u16 },
 }
 
-impl std::error::Error for Error {}
+impl Error {
+    /// Whether the request may succeed when tried again
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Fetch(err) => err.is_timeout() || err.is_connect(),
+            Self::RateLimited { .. } => true,
+            Self::Status { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fetch(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Fetch(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -30,6 +97,70 @@ impl fmt::Display for Error {
             Self::Transaction => write!(f, "Database transaction error"),
             Self::SQL => write!(f, "Something went wrong while processing the SQL statement"),
             Self::Field => write!(f, "Could not refference the database table field"),
+            Self::Migration { table, version } => {
+                write!(f, "Could not migrate table {table} to version {version}")
+            }
+            Self::Fetch(err) => write!(f, "Could not download page: {err}"),
+            Self::Parse(err) => write!(f, "Could not parse page: {err}"),
+            Self::Fixture(path) => write!(f, "No recorded page at {}", path.display()),
+            Self::Io(err) => write!(f, "File system error: {err}"),
+            Self::NotFound(url) => write!(f, "Page not found: {url}"),
+            Self::UnknownSymbol(symbol) => write!(f, "No provider id set for symbol {symbol}"),
+            Self::RateLimited { url, .. } => write!(f, "Rate limited while fetching {url}"),
+            Self::Blocked(url) => write!(f, "Access blocked while fetching {url}"),
+            Self::Interstitial { url, kind } => write!(f, "Got a {kind} while fetching {url}"),
+            Self::Status { url, status } => write!(f, "HTTP status {status} for {url}"),
+        }
+    }
+}
+
+/// Errors found while parsing a statement page
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// Invalid CSS selector
+    Selector(String),
+    /// Element matching `selector` not found in a table cell
+    Element {
+        selector: String,
+        row: usize,
+        column: usize,
+        html: String,
+    },
+    /// Column header that is not a term date
+    Term(String),
+    /// Cell value that is not a number
+    Value {
+        title: String,
+        term: String,
+        text: String,
+    },
+    /// Timeseries or company facts JSON that is missing or malformed
+    Json(String),
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Selector(selector) => write!(f, "Invalid selector `{selector}`"),
+            Self::Element {
+                selector,
+                row,
+                column,
+                html,
+            } => write!(
+                f,
+                "No `{selector}` element in row {row}, column {column}: {html}"
+            ),
+            Self::Term(text) => write!(f, "Column header is not a term date: {text:?}"),
+            Self::Value { title, term, text } => {
+                write!(
+                    f,
+                    "Value of \"{title}\" for {term} is not a number: {text:?}"
+                )
+            }
+            Self::Json(err) => write!(f, "Invalid JSON: {err}"),
         }
     }
 }
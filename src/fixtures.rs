@@ -0,0 +1,192 @@
+//! Recording and replaying of downloaded pages
+//!
+//! In record mode every page fetched by a [`SpiderClient`](crate::SpiderClient)
+//! is saved to a directory together with a small metadata file. In
+//! replay mode pages are served from that directory and the network is
+//! never touched, which keeps tests deterministic.
+//!
+//! Bodies are saved as `{name}.html`, or `{name}.json` for JSON
+//! responses, and their metadata as `{name}.meta.json`.
+
+use crate::error::Error;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longest file name kept as is
+const MAX_NAME: usize = 160;
+
+/// What to do with the fixtures directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Save every downloaded page
+    Record,
+    /// Serve pages from the directory instead of downloading them
+    Replay,
+}
+
+/// Metadata saved next to every recorded page
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureMeta {
+    pub url: String,
+    /// When the page was recorded, missing for synthetic pages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetched: Option<DateTime<Utc>>,
+    /// Whether the page was written by hand instead of recorded
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub synthetic: bool,
+}
+
+/// Directory of recorded pages
+#[derive(Debug, Clone)]
+pub struct Fixtures {
+    dir: PathBuf,
+    mode: FixtureMode,
+}
+
+impl Fixtures {
+    pub fn new(dir: impl Into<PathBuf>, mode: FixtureMode) -> Self {
+        Fixtures {
+            dir: dir.into(),
+            mode,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn mode(&self) -> FixtureMode {
+        self.mode
+    }
+
+    /// File name of the page recorded for `url`. Only the path and query
+    /// are used, so fixtures replay against any base url.
+    pub fn name(url: &str) -> String {
+        let path = match url.split_once("://") {
+            Some((_, rest)) => rest.find('/').map_or("", |start| &rest[start..]),
+            None => url,
+        };
+
+        let name: String = path
+            .trim_matches('/')
+            .chars()
+            .map(|c| match c {
+                '/' | '?' | '&' | '=' => '_',
+                c => c,
+            })
+            .collect();
+
+        // Long urls, such as timeseries queries, would exceed the file
+        // name limit
+        if name.len() > MAX_NAME {
+            let hash = blake3::hash(name.as_bytes()).to_hex();
+            let start: String = name.chars().take(MAX_NAME / 2).collect();
+            format!("{start}_{}", &hash[..16])
+        } else {
+            name
+        }
+    }
+
+    /// Read the page recorded for `url`
+    pub fn load(&self, url: &str) -> Result<String, Error> {
+        let name = Fixtures::name(url);
+        let html = self.dir.join(format!("{name}.html"));
+
+        fs::read_to_string(&html)
+            .or_else(|_| fs::read_to_string(self.dir.join(format!("{name}.json"))))
+            .map_err(|_| Error::Fixture(html))
+    }
+
+    /// Read the metadata recorded for `url`
+    pub fn load_meta(&self, url: &str) -> Result<FixtureMeta, Error> {
+        let path = self.dir.join(format!("{}.meta.json", Fixtures::name(url)));
+        let meta = fs::read_to_string(&path).map_err(|_| Error::Fixture(path.clone()))?;
+        serde_json::from_str(&meta).map_err(|_| Error::Fixture(path))
+    }
+
+    /// Record `body` as the page for `url`
+    pub fn save(&self, url: &str, body: &str) -> Result<(), Error> {
+        let name = Fixtures::name(url);
+        let meta = FixtureMeta {
+            url: url.to_string(),
+            fetched: Some(Utc::now()),
+            synthetic: false,
+        };
+        let meta =
+            serde_json::to_string_pretty(&meta).map_err(|_| Error::Fixture(self.dir.clone()))?;
+        let extension = if serde_json::from_str::<serde_json::Value>(body).is_ok() {
+            "json"
+        } else {
+            "html"
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(format!("{name}.{extension}")), body)?;
+        fs::write(self.dir.join(format!("{name}.meta.json")), meta)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures_record_replay() {
+        let dir = std::env::temp_dir().join(format!("finspider-fixtures-{}", std::process::id()));
+        let url = "https://finance.yahoo.com/quote/SBKP.JO/balance-sheet?frequency=quarterly";
+
+        assert_eq!(
+            Fixtures::name(url),
+            "quote_SBKP.JO_balance-sheet_frequency_quarterly"
+        );
+
+        let long = format!("{url}&type={}", "annualTotalAssets,".repeat(20));
+        assert!(Fixtures::name(&long).len() < MAX_NAME);
+        assert_ne!(Fixtures::name(&long), Fixtures::name(&format!("{long},")));
+
+        Fixtures::new(&dir, FixtureMode::Record)
+            .save(url, "<html></html>")
+            .unwrap();
+
+        let fixtures = Fixtures::new(&dir, FixtureMode::Replay);
+        assert_eq!(fixtures.load(url).unwrap(), "<html></html>");
+        assert_eq!(
+            fixtures
+                .load("http://127.0.0.1:8080/quote/SBKP.JO/balance-sheet?frequency=quarterly")
+                .unwrap(),
+            "<html></html>"
+        );
+        let meta = fixtures.load_meta(url).unwrap();
+        assert_eq!(meta.url, url);
+        assert!(meta.fetched.is_some());
+        assert!(!meta.synthetic);
+
+        // JSON responses keep their extension
+        let json = "https://query2.finance.yahoo.com/ws/fundamentals-timeseries/v1/finance/timeseries/SBKP.JO";
+        Fixtures::new(&dir, FixtureMode::Record)
+            .save(json, r#"{"timeseries":{}}"#)
+            .unwrap();
+        assert!(dir.join(format!("{}.json", Fixtures::name(json))).exists());
+        assert_eq!(fixtures.load(json).unwrap(), r#"{"timeseries":{}}"#);
+        assert!(matches!(
+            fixtures.load("https://finance.yahoo.com/quote/SBKP.JO/cash-flow"),
+            Err(Error::Fixture(_))
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+
+        // The checked in pages were written by hand
+        let checked_in = Fixtures::new(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/yahoo"),
+            FixtureMode::Replay,
+        );
+        let meta = checked_in.load_meta(url).unwrap();
+        assert!(meta.synthetic);
+        assert_eq!(meta.fetched, None);
+    }
+}
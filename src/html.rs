@@ -0,0 +1,169 @@
+//! Helpers shared by the statement page parsers
+
+use crate::error::ParseError;
+use crate::TermKind;
+use chrono::NaiveDate;
+use scraper::{ElementRef, Html, Selector};
+
+/// Compile a CSS selector
+pub(crate) fn selector(selectors: &str) -> Result<Selector, ParseError> {
+    Selector::parse(selectors).map_err(|_| ParseError::Selector(selectors.to_string()))
+}
+
+/// Separates the titles in the path of a sub-row
+pub(crate) const PATH_SEPARATOR: &str = " > ";
+
+/// A row of a statement table
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Row {
+    pub title: String,
+    /// Titles of the rows this one is a sub-row of, outermost first
+    pub parents: Vec<String>,
+    /// Text of the value columns, in order
+    pub values: Vec<String>,
+}
+
+impl Row {
+    /// Title prefixed with the titles of its parents, as in
+    /// `Total Assets > Current Assets`
+    pub fn path(&self) -> String {
+        self.parents
+            .iter()
+            .chain([&self.title])
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(PATH_SEPARATOR)
+    }
+}
+
+/// Read every row of the statement table, including the sub-rows that
+/// are collapsed on the page. Sub-rows are either siblings of their
+/// parent row, with a deeper `lv-N` class, or nested inside it.
+pub(crate) fn rows(document: &Html) -> Result<Vec<Row>, ParseError> {
+    let rows = selector(".tableBody .row")?;
+    let columns = selector(".column")?;
+    let row_title = selector(".rowTitle")?;
+    let mut res = vec![];
+    // Depth and title of the rows enclosing the current one
+    let mut open: Vec<(usize, String)> = vec![];
+
+    for (row_count, row) in document.select(&rows).enumerate() {
+        // Columns of nested rows belong to those rows
+        let own_columns: Vec<ElementRef> = row
+            .select(&columns)
+            .filter(|column| enclosing_row(*column).map(|row| row.id()) == Some(row.id()))
+            .collect();
+
+        let title = own_columns
+            .first()
+            .and_then(|column| column.select(&row_title).next())
+            .ok_or_else(|| ParseError::Element {
+                selector: ".rowTitle".to_string(),
+                row: row_count,
+                column: 0,
+                html: own_columns
+                    .first()
+                    .map_or_else(|| row.html(), |column| column.html()),
+            })?;
+
+        let title = text(title);
+        let depth = depth(row);
+        while open.last().is_some_and(|(parent, _)| *parent >= depth) {
+            open.pop();
+        }
+        let parents = open.iter().map(|(_, title)| title.clone()).collect();
+        open.push((depth, title.clone()));
+
+        res.push(Row {
+            title,
+            parents,
+            values: own_columns
+                .iter()
+                .skip(1)
+                .map(|column| text(*column))
+                .collect(),
+        });
+    }
+
+    Ok(res)
+}
+
+/// Column header of a statement table
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Term {
+    /// Header as shown on the page
+    pub text: String,
+    pub date: NaiveDate,
+    pub kind: TermKind,
+}
+
+/// Term with the row titles and values of its line items
+pub(crate) type Column = (Term, Vec<String>, Vec<String>);
+
+/// Read the headers of the value columns. The TTM column has no date of
+/// its own and is dated `ttm`, the end of the trailing twelve months when
+/// known, and otherwise as of the latest reported column. It is `None`
+/// when the page has no date to give it.
+pub(crate) fn terms(
+    document: &Html,
+    ttm: Option<NaiveDate>,
+) -> Result<Vec<Option<Term>>, ParseError> {
+    let headers = selector(".tableHeader .column")?;
+    let texts: Vec<String> = document.select(&headers).skip(1).map(text).collect();
+    let date = |text: &str| NaiveDate::parse_from_str(text, "%m/%d/%Y").ok();
+    let as_of = ttm.or_else(|| texts.iter().filter_map(|text| date(text)).max());
+
+    texts
+        .into_iter()
+        .map(|text| match text.as_str() {
+            "TTM" => Ok(as_of.map(|date| Term {
+                text,
+                date,
+                kind: TermKind::Ttm,
+            })),
+            _ => match date(&text) {
+                Some(date) => Ok(Some(Term {
+                    text,
+                    date,
+                    kind: TermKind::Reported,
+                })),
+                None => Err(ParseError::Term(text)),
+            },
+        })
+        .collect()
+}
+
+pub(crate) fn text(element: ElementRef) -> String {
+    element
+        .text()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim()
+        .to_string()
+}
+
+fn is_row(element: ElementRef) -> bool {
+    element.value().classes().any(|class| class == "row")
+}
+
+/// Level of `row`, 0 for top level rows, from its `lv-N` class or else from the rows it is
+/// nested in
+fn depth(row: ElementRef) -> usize {
+    row.value()
+        .classes()
+        .find_map(|class| class.strip_prefix("lv-")?.parse().ok())
+        .unwrap_or_else(|| {
+            row.ancestors()
+                .filter_map(ElementRef::wrap)
+                .filter(|element| is_row(*element))
+                .count()
+        })
+}
+
+/// Innermost row containing `element`
+fn enclosing_row(element: ElementRef) -> Option<ElementRef> {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|element| is_row(*element))
+}
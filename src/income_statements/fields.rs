@@ -0,0 +1,611 @@
+//! Known line items of the income statement
+
+use crate::statement::{FieldMeta, Sign, Unit};
+
+/// Every known income statement line item, in declaration order
+pub const FIELDS: &[FieldMeta] = &[
+    FieldMeta {
+        name: "total_revenue",
+        title: "Total Revenue",
+        key: "TotalRevenue",
+        description: "Income from the sale of goods and services",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "income_from_associates_and_other_participating_interests",
+        title: "Income from Associates & Other Participating Interests",
+        key: "IncomeFromAssociatesandOtherParticipatingInterests",
+        description: "Share of the profits of associates and joint ventures",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "special_income_charges",
+        title: "Special Income Charges",
+        key: "SpecialIncomeCharges",
+        description: "Non recurring income and charges such as impairments",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: Some("other_income_expense"),
+    },
+    FieldMeta {
+        name: "other_non_operating_income_expenses",
+        title: "Other Non Operating Income Expenses",
+        key: "OtherNonOperatingIncomeExpenses",
+        description: "Other income and expenses outside the normal business",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: Some("other_income_expense"),
+    },
+    FieldMeta {
+        name: "pretax_income",
+        title: "Pretax Income",
+        key: "PretaxIncome",
+        description: "Income before tax",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "tax_provision",
+        title: "Tax Provision",
+        key: "TaxProvision",
+        description: "Income tax expense",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "net_income_common_stockholders",
+        title: "Net Income Common Stockholders",
+        key: "NetIncomeCommonStockholders",
+        description: "Net income attributable to common shareholders",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "net_income_from_continuing_operation_net_minority_interest",
+        title: "Net Income from Continuing Operation Net Minority Interest",
+        key: "NetIncomeFromContinuingOperationNetMinorityInterest",
+        description: "Net income from continuing operations, excluding minority interest",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "diluted_ni_available_to_com_stockholders",
+        title: "Diluted NI Available to Com Stockholders",
+        key: "DilutedNIAvailtoComStockholders",
+        description: "Net income available to common shareholders after dilution",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "net_from_continuing_and_discontinued_operation",
+        title: "Net Income from Continuing & Discontinued Operation",
+        key: "NetIncomeFromContinuingAndDiscontinuedOperation",
+        description: "Net income from continuing and discontinued operations",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "normalized_income",
+        title: "Normalized Income",
+        key: "NormalizedIncome",
+        description: "Net income excluding unusual items",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "reconciled_depreciation",
+        title: "Reconciled Depreciation",
+        key: "ReconciledDepreciation",
+        description: "Depreciation and amortization",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "total_unusual_items_excluding_goodwill",
+        title: "Total Unusual Items Excluding Goodwill",
+        key: "TotalUnusualItemsExcludingGoodwill",
+        description: "Unusual items other than goodwill impairments",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: Some("total_unusual_items"),
+    },
+    FieldMeta {
+        name: "total_unusual_items",
+        title: "Total Unusual Items",
+        key: "TotalUnusualItems",
+        description: "Income and charges that are not expected to recur",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "tax_rate_for_calcs",
+        title: "Tax Rate for Calcs",
+        key: "TaxRateForCalcs",
+        description: "Effective tax rate used for normalized figures",
+        unit: Unit::Ratio,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "tax_effect_of_unusual_items",
+        title: "Tax Effect of Unusual Items",
+        key: "TaxEffectOfUnusualItems",
+        description: "Tax on the unusual items",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "cost_of_revenue",
+        title: "Cost of Revenue",
+        key: "CostOfRevenue",
+        description: "Direct costs of the goods and services sold",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("total_expenses"),
+    },
+    FieldMeta {
+        name: "gross_profit",
+        title: "Gross Profit",
+        key: "GrossProfit",
+        description: "Total revenue less cost of revenue",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "operating_expense",
+        title: "Operating Expense",
+        key: "OperatingExpense",
+        description: "Costs of running the business other than cost of revenue",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("total_expenses"),
+    },
+    FieldMeta {
+        name: "operating_income",
+        title: "Operating Income",
+        key: "OperatingIncome",
+        description: "Gross profit less operating expense",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "net_non_operating_interest_income_expense",
+        title: "Net Non Operating Interest Income Expense",
+        key: "NetNonOperatingInterestIncomeExpense",
+        description: "Interest income less interest expense outside the normal business",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "other_income_expense",
+        title: "Other Income Expense",
+        key: "OtherIncomeExpense",
+        description: "Income and expenses outside the normal business",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "basic_eps",
+        title: "Basic EPS",
+        key: "BasicEPS",
+        description: "Net income per basic share",
+        unit: Unit::PerShare,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "diluted_eps",
+        title: "Diluted EPS",
+        key: "DilutedEPS",
+        description: "Net income per diluted share",
+        unit: Unit::PerShare,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "basic_average_shares",
+        title: "Basic Average Shares",
+        key: "BasicAverageShares",
+        description: "Weighted average number of shares outstanding",
+        unit: Unit::Shares,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "diluted_average_shares",
+        title: "Diluted Average Shares",
+        key: "DilutedAverageShares",
+        description: "Weighted average number of shares including dilution",
+        unit: Unit::Shares,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "total_operating_income_as_reported",
+        title: "Total Operating Income as Reported",
+        key: "TotalOperatingIncomeAsReported",
+        description: "Operating income as reported by the company",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "total_expenses",
+        title: "Total Expenses",
+        key: "TotalExpenses",
+        description: "Cost of revenue plus operating expense",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "interest_income",
+        title: "Interest Income",
+        key: "InterestIncome",
+        description: "Interest earned",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("net_interest_income"),
+    },
+    FieldMeta {
+        name: "interest_expense",
+        title: "Interest Expense",
+        key: "InterestExpense",
+        description: "Interest owed on debt",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("net_interest_income"),
+    },
+    FieldMeta {
+        name: "net_interest_income",
+        title: "Net Interest Income",
+        key: "NetInterestIncome",
+        description: "Interest income less interest expense",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: true,
+        parent: None,
+    },
+    FieldMeta {
+        name: "ebit",
+        title: "EBIT",
+        key: "EBIT",
+        description: "Earnings before interest and tax",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "ebitda",
+        title: "EBITDA",
+        key: "EBITDA",
+        description: "Earnings before interest, tax, depreciation and amortization",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "reconciled_cost_of_revenue",
+        title: "Reconciled Cost of Revenue",
+        key: "ReconciledCostOfRevenue",
+        description: "Cost of revenue adjusted for depreciation",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "normalized_ebitda",
+        title: "Normalized EBITDA",
+        key: "NormalizedEBITDA",
+        description: "EBITDA excluding unusual items",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "average_dilution_earnings",
+        title: "Average Dilution Earnings",
+        key: "AverageDilutionEarnings",
+        description: "Adjustment to earnings for dilutive securities",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "credit_losses_provision",
+        title: "Credit Losses Provision",
+        key: "CreditLossesProvision",
+        description: "Provision for bad loans",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "non_interest_expense",
+        title: "Non Interest Expense",
+        key: "NonInterestExpense",
+        description: "Expenses of a bank other than interest",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "rent_expense_supplemental",
+        title: "Rent Expense Supplemental",
+        key: "RentExpenseSupplemental",
+        description: "Rent and lease expense",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "interest_income_after_provision_for_loan_loss",
+        title: "Interest Income after Provision for Loan Loss",
+        key: "InterestIncomeAfterProvisionForLoanLoss",
+        description: "Net interest income less the provision for credit losses",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "total_money_market_investments",
+        title: "Total Money Market Investments",
+        key: "TotalMoneyMarketInvestments",
+        description: "Income from money market investments",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "earnings_from_equity_interest_net_of_tax",
+        title: "Earnings from Equity Interest Net of Tax",
+        key: "EarningsFromEquityInterestNetOfTax",
+        description: "Share of the profits of equity investments after tax",
+        unit: Unit::Currency,
+        sign: Sign::Either,
+        subtotal: false,
+        parent: None,
+    },
+    FieldMeta {
+        name: "operating_revenue",
+        title: "Operating Revenue",
+        key: "OperatingRevenue",
+        description: "Revenue from the normal business operations",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("total_revenue"),
+    },
+    FieldMeta {
+        name: "selling_general_and_administration",
+        title: "Selling General and Administration",
+        key: "SellingGeneralAndAdministration",
+        description: "Selling, marketing and administrative costs",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: true,
+        parent: Some("operating_expense"),
+    },
+    FieldMeta {
+        name: "general_and_administrative_expense",
+        title: "General & Administrative Expense",
+        key: "GeneralAndAdministrativeExpense",
+        description: "Costs of running the company",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("selling_general_and_administration"),
+    },
+    FieldMeta {
+        name: "selling_and_marketing_expense",
+        title: "Selling & Marketing Expense",
+        key: "SellingAndMarketingExpense",
+        description: "Costs of selling and promoting products",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("selling_general_and_administration"),
+    },
+    FieldMeta {
+        name: "research_and_development",
+        title: "Research & Development",
+        key: "ResearchAndDevelopment",
+        description: "Costs of developing new products",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("operating_expense"),
+    },
+    FieldMeta {
+        name: "depreciation_and_amortization_in_income_statement",
+        title: "Depreciation And Amortization In Income Statement",
+        key: "DepreciationAndAmortizationInIncomeStatement",
+        description: "Depreciation and amortization included in operating expense",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("operating_expense"),
+    },
+    FieldMeta {
+        name: "interest_income_non_operating",
+        title: "Interest Income Non Operating",
+        key: "InterestIncomeNonOperating",
+        description: "Interest earned outside the normal business",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("net_non_operating_interest_income_expense"),
+    },
+    FieldMeta {
+        name: "interest_expense_non_operating",
+        title: "Interest Expense Non Operating",
+        key: "InterestExpenseNonOperating",
+        description: "Interest owed outside the normal business",
+        unit: Unit::Currency,
+        sign: Sign::Positive,
+        subtotal: false,
+        parent: Some("net_non_operating_interest_income_expense"),
+    },
+];
+
+/// Investing.com row titles and the fields they are stored in
+pub const INVESTING: &[(&str, &str)] = &[
+    ("Total Revenue", "total_revenue"),
+    ("Revenue", "operating_revenue"),
+    ("Cost of Revenue, Total", "cost_of_revenue"),
+    ("Gross Profit", "gross_profit"),
+    ("Total Operating Expenses", "total_expenses"),
+    (
+        "Selling/General/Admin. Expenses, Total",
+        "selling_general_and_administration",
+    ),
+    ("Research & Development", "research_and_development"),
+    (
+        "Depreciation / Amortization",
+        "depreciation_and_amortization_in_income_statement",
+    ),
+    ("Unusual Expense (Income)", "special_income_charges"),
+    ("Operating Income", "operating_income"),
+    (
+        "Interest Income (Expense), Net Non-Operating",
+        "net_non_operating_interest_income_expense",
+    ),
+    ("Other, Net", "other_non_operating_income_expenses"),
+    ("Net Income Before Taxes", "pretax_income"),
+    ("Provision for Income Taxes", "tax_provision"),
+    (
+        "Net Income After Taxes",
+        "net_income_from_continuing_operation_net_minority_interest",
+    ),
+    ("Net Income", "net_income_common_stockholders"),
+    (
+        "Diluted Net Income",
+        "diluted_ni_available_to_com_stockholders",
+    ),
+    ("Diluted Weighted Average Shares", "diluted_average_shares"),
+    ("Diluted EPS Excluding ExtraOrd Items", "diluted_eps"),
+];
+
+/// US-GAAP tags and the fields they are stored in, preferred tags first
+pub const US_GAAP: &[(&str, &str)] = &[
+    ("Revenues", "total_revenue"),
+    (
+        "RevenueFromContractWithCustomerExcludingAssessedTax",
+        "total_revenue",
+    ),
+    ("SalesRevenueNet", "total_revenue"),
+    (
+        "IncomeLossFromContinuingOperationsBeforeIncomeTaxesExtraordinaryItemsNoncontrollingInterest",
+        "pretax_income",
+    ),
+    (
+        "IncomeLossFromContinuingOperationsBeforeIncomeTaxesMinorityInterestAndIncomeLossFromEquityMethodInvestments",
+        "pretax_income",
+    ),
+    ("IncomeTaxExpenseBenefit", "tax_provision"),
+    (
+        "NetIncomeLossAvailableToCommonStockholdersBasic",
+        "net_income_common_stockholders",
+    ),
+    ("NetIncomeLoss", "net_income_common_stockholders"),
+    (
+        "IncomeLossFromContinuingOperations",
+        "net_income_from_continuing_operation_net_minority_interest",
+    ),
+    (
+        "NetIncomeLossAvailableToCommonStockholdersDiluted",
+        "diluted_ni_available_to_com_stockholders",
+    ),
+    ("CostOfRevenue", "cost_of_revenue"),
+    ("CostOfGoodsAndServicesSold", "cost_of_revenue"),
+    ("GrossProfit", "gross_profit"),
+    ("OperatingExpenses", "operating_expense"),
+    ("OperatingIncomeLoss", "operating_income"),
+    (
+        "InterestIncomeExpenseNonoperatingNet",
+        "net_non_operating_interest_income_expense",
+    ),
+    ("NonoperatingIncomeExpense", "other_income_expense"),
+    ("EarningsPerShareBasic", "basic_eps"),
+    ("EarningsPerShareDiluted", "diluted_eps"),
+    (
+        "WeightedAverageNumberOfSharesOutstandingBasic",
+        "basic_average_shares",
+    ),
+    (
+        "WeightedAverageNumberOfDilutedSharesOutstanding",
+        "diluted_average_shares",
+    ),
+    ("CostsAndExpenses", "total_expenses"),
+    ("InvestmentIncomeInterest", "interest_income"),
+    ("InterestExpense", "interest_expense"),
+    ("OperatingLeaseExpense", "rent_expense_supplemental"),
+    (
+        "SellingGeneralAndAdministrativeExpense",
+        "selling_general_and_administration",
+    ),
+    (
+        "GeneralAndAdministrativeExpense",
+        "general_and_administrative_expense",
+    ),
+    ("SellingAndMarketingExpense", "selling_and_marketing_expense"),
+    ("ResearchAndDevelopmentExpense", "research_and_development"),
+];
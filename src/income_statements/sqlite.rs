@@ -0,0 +1,98 @@
+use super::IncomeStatement;
+use crate::error::Error;
+use crate::sqlite;
+use crate::store::{OnConflict, Saved};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::Connection;
+
+/// SQLite storage for income statements, with the same semantics as the
+/// PostgreSQL one
+pub struct IncomeStatementsSqlite;
+impl IncomeStatementsSqlite {
+    /// Initialize SQLite cache
+    pub fn init(conn: &Connection, table_name: &str) -> Result<(), Error> {
+        sqlite::init::<IncomeStatement>(conn, table_name)
+    }
+
+    /// Save income statement in database
+    pub fn save(
+        conn: &Connection,
+        table_name: &str,
+        income_statement: IncomeStatement,
+    ) -> Result<(), Error> {
+        sqlite::save(conn, table_name, &income_statement)
+    }
+
+    /// Save income statement in database, doing `on_conflict` when one with the
+    /// same hash is already stored
+    pub fn upsert(
+        conn: &Connection,
+        table_name: &str,
+        income_statement: IncomeStatement,
+        on_conflict: OnConflict,
+    ) -> Result<Saved, Error> {
+        sqlite::upsert(conn, table_name, &income_statement, on_conflict)
+    }
+
+    pub fn read(
+        conn: &Connection,
+        table_name: &str,
+        hash: &str,
+    ) -> Result<Option<IncomeStatement>, Error> {
+        sqlite::read(conn, table_name, hash)
+    }
+
+    pub fn read_all(conn: &Connection, table_name: &str) -> Result<Vec<IncomeStatement>, Error> {
+        sqlite::read_all(conn, table_name)
+    }
+
+    pub fn read_all_by_symbol(
+        conn: &Connection,
+        table_name: &str,
+        symbol: &str,
+    ) -> Result<Vec<IncomeStatement>, Error> {
+        sqlite::read_all_by_symbol(conn, table_name, symbol)
+    }
+
+    /// Read the income statements of `symbol` with a term from `from` to `to`,
+    /// oldest first
+    pub fn read_range(
+        conn: &Connection,
+        table_name: &str,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<IncomeStatement>, Error> {
+        sqlite::read_range(conn, table_name, symbol, from, to)
+    }
+
+    /// Save income statement in database as the current version of its term from
+    /// `at`, moving the version it restates to the history
+    pub fn revise(
+        conn: &Connection,
+        table_name: &str,
+        income_statement: IncomeStatement,
+        at: DateTime<Utc>,
+    ) -> Result<Saved, Error> {
+        sqlite::revise(conn, table_name, &income_statement, at)
+    }
+
+    /// Read the income statements of `symbol` with a term from `from` to `to` as
+    /// they were stored at `at`, oldest first
+    pub fn read_as_of(
+        conn: &Connection,
+        table_name: &str,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<IncomeStatement>, Error> {
+        sqlite::read_as_of(conn, table_name, symbol, from, to, at)
+    }
+
+    /// Delete the statement identified by `hash`, returning whether it
+    /// was stored
+    pub fn delete(conn: &Connection, table_name: &str, hash: &str) -> Result<bool, Error> {
+        sqlite::delete(conn, table_name, hash)
+    }
+}
@@ -0,0 +1,352 @@
+//! Statements from Investing.com
+//!
+//! Investing.com serves its statement tables as page fragments, selected
+//! by the numeric pair id of the instrument rather than its symbol. The
+//! endpoint only answers requests made like its own page's, with an
+//! `X-Requested-With: XMLHttpRequest` header, which the provider sends.
+
+use crate::balance_sheets::{self, BalanceSheet};
+use crate::cash_flows::{self, CashFlow};
+use crate::error::{Error, ParseError};
+use crate::html::{self, Column, Term};
+use crate::income_statements::{self, IncomeStatement};
+use crate::provider::Provider;
+use crate::statement::{self, FinancialStatement, StatementType, Unit};
+use crate::{value, Decimal, Period, Source, SpiderClient, TermKind, INVESTING_ROOT};
+use chrono::NaiveDate;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use scraper::{ElementRef, Html};
+use std::collections::HashMap;
+
+/// Report type requested from Investing.com
+pub fn report_type(statement: StatementType) -> &'static str {
+    match statement {
+        StatementType::BalanceSheet => "BAL",
+        StatementType::CashFlow => "CAS",
+        StatementType::IncomeStatement => "INC",
+    }
+}
+
+/// Investing.com row titles and the fields they are stored in
+pub fn titles(statement: StatementType) -> &'static [(&'static str, &'static str)] {
+    match statement {
+        StatementType::BalanceSheet => balance_sheets::fields::INVESTING,
+        StatementType::CashFlow => cash_flows::fields::INVESTING,
+        StatementType::IncomeStatement => income_statements::fields::INVESTING,
+    }
+}
+
+/// Url of the `period` statement table of `S` for the instrument `pair_id`
+pub fn url<S: FinancialStatement>(root: &str, pair_id: &str, period: Period) -> String {
+    let period_type = match period {
+        Period::Annual => "Annual",
+        Period::Quarterly => "Interim",
+    };
+
+    format!(
+        "{root}/instruments/Financials/changereporttypeajax?action=change_report_type&pair_ID={pair_id}&report_type={}&period_type={period_type}",
+        report_type(S::TYPE)
+    )
+}
+
+/// Read the columns of the statement table, newest first. Known rows
+/// are titled like on Yahoo and unknown rows keep their own title.
+///
+/// Investing.com reports amounts and share counts in millions. They are
+/// converted to thousands, the unit of Yahoo's statement pages, so both
+/// providers can be compared.
+fn columns<S: FinancialStatement>(html: &str) -> Result<Vec<Column>, ParseError> {
+    let document = Html::parse_document(html);
+    let rows = html::selector("table.reportTbl tr")?;
+    let tables = html::selector("table")?;
+    let known = titles(S::TYPE);
+    let mut terms = vec![];
+    let mut titles = vec![];
+    let mut cells: Vec<Vec<String>> = vec![];
+
+    for row in document.select(&rows) {
+        let headers = children(row, "th");
+
+        // The first header row has the term dates, the next ones their lengths
+        if !headers.is_empty() {
+            if terms.is_empty() {
+                terms = headers
+                    .into_iter()
+                    .skip(1)
+                    .map(term)
+                    .collect::<Result<_, _>>()?;
+            }
+            continue;
+        }
+
+        let mut values = children(row, "td").into_iter();
+        let Some(title) = values.next() else {
+            continue;
+        };
+
+        // Rows holding the table of collapsed sub-rows
+        if title.select(&tables).next().is_some() {
+            continue;
+        }
+
+        let title = html::text(title);
+        let field = known
+            .iter()
+            .find(|(investing, _)| *investing == title)
+            .and_then(|(_, name)| S::field_meta(name));
+        let (title, scale) = match field {
+            Some(field) => (
+                field.title.to_string(),
+                matches!(field.unit, Unit::Currency | Unit::Shares),
+            ),
+            None => (title, true),
+        };
+
+        cells.push(
+            values
+                .zip(&terms)
+                .map(|(cell, term)| scaled(&title, term, &html::text(cell), scale))
+                .collect::<Result<_, _>>()?,
+        );
+        titles.push(title);
+    }
+
+    Ok(terms
+        .into_iter()
+        .enumerate()
+        .map(|(column, term)| {
+            let values = cells
+                .iter()
+                .map(|row| row.get(column).cloned().unwrap_or_default())
+                .collect();
+
+            (term, titles.clone(), values)
+        })
+        .collect())
+}
+
+/// Parse all the statements in a downloaded statement table
+pub fn parse<S: FinancialStatement>(
+    html: &str,
+    symbol: &str,
+    period: Period,
+) -> Result<Vec<S>, ParseError> {
+    columns::<S>(html)?
+        .into_iter()
+        .map(|(term, titles, values)| {
+            statement::from_column(&titles, values, &term, symbol, period, Source::Investing)
+        })
+        .collect()
+}
+
+/// Statements from Investing.com
+#[derive(Debug, Clone)]
+pub struct InvestingProvider {
+    client: SpiderClient,
+    base_url: String,
+    pair_ids: HashMap<String, String>,
+}
+
+impl InvestingProvider {
+    pub fn new(client: SpiderClient) -> Self {
+        InvestingProvider {
+            client,
+            base_url: INVESTING_ROOT.to_string(),
+            pair_ids: HashMap::new(),
+        }
+    }
+
+    /// Root url of Investing.com, defaults to [`INVESTING_ROOT`]
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Investing.com pair id of `symbol`. Symbols without one can only be
+    /// fetched when they are a pair id themselves.
+    pub fn pair_id(mut self, symbol: &str, pair_id: &str) -> Self {
+        self.pair_ids
+            .insert(symbol.to_string(), pair_id.to_string());
+        self
+    }
+
+    /// Download and parse the `period` statements of `S` for `symbol`
+    pub fn fetch<S: FinancialStatement>(
+        &self,
+        symbol: &str,
+        period: Period,
+    ) -> Result<Vec<S>, Error> {
+        let pair_id = match self.pair_ids.get(symbol) {
+            Some(pair_id) => pair_id.as_str(),
+            None if symbol.bytes().all(|byte| byte.is_ascii_digit()) => symbol,
+            None => return Err(Error::UnknownSymbol(symbol.to_string())),
+        };
+        let url = url::<S>(&self.base_url, pair_id, period);
+        println!(
+            "---> Fetching Investing.com {} ({period}) for: {symbol}",
+            S::NAME
+        );
+        println!("---> {url}");
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("x-requested-with"),
+            HeaderValue::from_static("XMLHttpRequest"),
+        );
+        let html = self.client.get_with(&url, &headers)?;
+        Ok(parse(&html, symbol, period)?)
+    }
+}
+
+impl Provider for InvestingProvider {
+    fn source(&self) -> Source {
+        Source::Investing
+    }
+
+    fn balance_sheets(&self, symbol: &str, period: Period) -> Result<Vec<BalanceSheet>, Error> {
+        self.fetch(symbol, period)
+    }
+
+    fn cash_flows(&self, symbol: &str, period: Period) -> Result<Vec<CashFlow>, Error> {
+        self.fetch(symbol, period)
+    }
+
+    fn income_statements(
+        &self,
+        symbol: &str,
+        period: Period,
+    ) -> Result<Vec<IncomeStatement>, Error> {
+        self.fetch(symbol, period)
+    }
+}
+
+/// Child elements of `row` called `name`
+fn children<'a>(row: ElementRef<'a>, name: &str) -> Vec<ElementRef<'a>> {
+    row.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|cell| cell.value().name() == name)
+        .collect()
+}
+
+/// Term of a header with the year above the day and month, as in
+/// `2023 31/12`
+fn term(header: ElementRef) -> Result<Term, ParseError> {
+    let text = html::text(header);
+    let date = match text.split_whitespace().collect::<Vec<_>>()[..] {
+        [year, day_month] => {
+            NaiveDate::parse_from_str(&format!("{day_month}/{year}"), "%d/%m/%Y").ok()
+        }
+        _ => None,
+    };
+
+    match date {
+        Some(date) => Ok(Term {
+            text,
+            date,
+            kind: TermKind::Reported,
+        }),
+        None => Err(ParseError::Term(text)),
+    }
+}
+
+/// Cell `text` in thousands when `scale` is set. Missing values (`"-"`)
+/// are left blank.
+fn scaled(title: &str, term: &Term, text: &str, scale: bool) -> Result<String, ParseError> {
+    let err = || ParseError::Value {
+        title: title.to_string(),
+        term: term.text.clone(),
+        text: text.to_string(),
+    };
+
+    if text == "-" {
+        return Ok(String::new());
+    }
+
+    match value::parse(text).map_err(|_| err())? {
+        Some(value) if scale => value
+            .checked_mul(Decimal::ONE_THOUSAND)
+            .map(|value| value.normalize().to_string())
+            .ok_or_else(err),
+        Some(value) => Ok(value.to_string()),
+        None => Ok(String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::rate_limit::RateLimiter;
+    use std::fs;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/investing");
+
+    #[test]
+    fn test_investing() {
+        let symbol = "SBKP.JO";
+        let client = SpiderClient::builder().replay(FIXTURES).build().unwrap();
+        let provider = InvestingProvider::new(client).pair_id(symbol, "41213");
+        let cash_flows = provider.cash_flows(symbol, Period::Annual).unwrap();
+
+        assert_eq!(cash_flows.len(), 4);
+        assert!(matches!(
+            InvestingProvider::new(SpiderClient::new().unwrap()).cash_flows(symbol, Period::Annual),
+            Err(Error::UnknownSymbol(_))
+        ));
+        assert!(cash_flows
+            .iter()
+            .all(|cash_flow| cash_flow.source == Source::Investing));
+        assert_eq!(cash_flows[0].symbol, symbol);
+        assert_eq!(
+            cash_flows[0].term,
+            NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+        );
+
+        // Millions are stored in thousands, like on Yahoo
+        assert_eq!(
+            cash_flows[0].operating_cash_flow,
+            Some(Decimal::new(48698000, 0))
+        );
+        assert_eq!(
+            cash_flows[0].capital_expenditure,
+            Some(Decimal::new(-6339000, 0))
+        );
+        assert_eq!(
+            cash_flows[0].extra.get("Foreign Exchange Effects"),
+            Some(&Some(Decimal::new(-1250500, 0)))
+        );
+        assert_eq!(cash_flows[3].cash_dividends_paid, None);
+
+        // Same numbers as Yahoo, but a statement of its own
+        let yahoo = CashFlow {
+            source: Source::Yahoo,
+            ..cash_flows[0].clone()
+        };
+        assert_ne!(yahoo.hash(), cash_flows[0].hash());
+
+        assert_eq!(
+            parse::<CashFlow>(
+                r#"<table class="genTbl reportTbl"><tr><th>Period Ending:</th><th>Latest</th></tr></table>"#,
+                symbol,
+                Period::Annual
+            ),
+            Err(ParseError::Term("Latest".to_string()))
+        );
+
+        // Requested like the page's own requests
+        let html = fs::read_to_string(format!(
+            "{FIXTURES}/instruments_Financials_changereporttypeajax_action_change_report_type_pair_ID_41213_report_type_CAS_period_type_Annual.html"
+        ))
+        .unwrap();
+        let server = MockServer::start(vec![MockResponse::ok(&html)]);
+        let client = SpiderClient::builder()
+            .rate_limit(RateLimiter::unlimited())
+            .build()
+            .unwrap();
+        let provider = InvestingProvider::new(client).base_url(&server.url);
+        assert_eq!(
+            provider.cash_flows("41213", Period::Annual).unwrap().len(),
+            4
+        );
+        assert!(server.requests()[0].contains(&"x-requested-with: XMLHttpRequest".to_string()));
+    }
+}
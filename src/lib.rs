@@ -1,16 +1,204 @@
+#[cfg(feature = "tokio")]
+pub mod async_client;
 #[cfg(feature = "finacials")]
 pub mod balance_sheets;
+pub mod cache;
 #[cfg(feature = "finacials")]
 pub mod cash_flows;
+pub mod client;
+pub mod consent;
+#[cfg(all(feature = "finacials", feature = "postgres"))]
+mod database;
+#[cfg(feature = "finacials")]
+pub mod edgar;
 pub mod error;
+pub mod fixtures;
+#[cfg(feature = "finacials")]
+mod html;
 #[cfg(feature = "finacials")]
 pub mod income_statements;
+#[cfg(feature = "finacials")]
+pub mod investing;
+#[cfg(all(feature = "finacials", feature = "postgres"))]
+pub mod migrations;
+#[cfg(test)]
+mod mock;
+#[cfg(feature = "finacials")]
+pub mod provider;
+pub mod rate_limit;
+pub mod retry;
+#[cfg(all(feature = "finacials", feature = "sqlite"))]
+mod sqlite;
+#[cfg(feature = "finacials")]
+pub mod statement;
+#[cfg(feature = "finacials")]
+pub mod store;
+#[cfg(feature = "finacials")]
+pub mod timeseries;
+pub mod value;
+#[cfg(feature = "finacials")]
+pub mod yahoo;
 
 pub const USER_AGENT: &str =
     "Mozilla/5.0 (Windows NT 6.1; WOW64; rv:47.0) Gecko/20100101 Firefox/47.0";
 pub const YAHOO_ROOT: &str = "https://finance.yahoo.com";
+pub const YAHOO_TIMESERIES_ROOT: &str = "https://query2.finance.yahoo.com";
 pub const INVESTING_ROOT: &str = "https://www.investing.com";
+pub const EDGAR_ROOT: &str = "https://data.sec.gov";
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "tokio")]
+pub use async_client::{AsyncSpider, AsyncSpiderClient};
+pub use client::{SpiderClient, SpiderClientBuilder};
+#[cfg(feature = "finacials")]
+pub use provider::Provider;
+#[cfg(feature = "finacials")]
+pub use statement::FinancialStatement;
+#[cfg(feature = "finacials")]
+pub use store::StatementStore;
+pub use value::Decimal;
+
+/// Reporting period covered by a financial statement
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Period {
+    #[default]
+    Annual,
+    Quarterly,
+}
+
+impl Period {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Annual => "annual",
+            Self::Quarterly => "quarterly",
+        }
+    }
+
+    /// Query string selecting the period on Yahoo statement pages
+    pub fn query(&self) -> &'static str {
+        match self {
+            Self::Annual => "",
+            Self::Quarterly => "?frequency=quarterly",
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Period {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "annual" => Ok(Self::Annual),
+            "quarterly" => Ok(Self::Quarterly),
+            _ => Err(error::Error::Field),
+        }
+    }
+}
+
+/// Whether a statement covers a reported term or the trailing twelve
+/// months up to its term
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum TermKind {
+    #[default]
+    Reported,
+    Ttm,
+}
+
+impl TermKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Reported => "reported",
+            Self::Ttm => "ttm",
+        }
+    }
+}
+
+impl fmt::Display for TermKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for TermKind {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reported" => Ok(Self::Reported),
+            "ttm" => Ok(Self::Ttm),
+            _ => Err(error::Error::Field),
+        }
+    }
+}
+
+/// Provider a statement was downloaded from
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Source {
+    #[default]
+    Yahoo,
+    Investing,
+    /// SEC EDGAR XBRL company facts
+    Edgar,
+    /// Any other provider, by name
+    Other(String),
+}
+
+impl Source {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Yahoo => "yahoo",
+            Self::Investing => "investing",
+            Self::Edgar => "edgar",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Source {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "yahoo" => Ok(Self::Yahoo),
+            "investing" => Ok(Self::Investing),
+            "edgar" => Ok(Self::Edgar),
+            "" => Err(error::Error::Field),
+            name => Ok(Self::Other(name.to_string())),
+        }
+    }
+}
 
 pub trait Spider {
-    fn fetch(symbol: &str) -> Result<String, reqwest::Error>;
+    /// Human readable name of the downloaded page
+    const NAME: &'static str;
+
+    /// Url of the `period` page for `symbol`, relative to `root`
+    fn url(root: &str, symbol: &str, period: Period) -> String;
+
+    /// Download the `period` page for `symbol` using a default
+    /// [`SpiderClient`]
+    fn fetch(symbol: &str, period: Period) -> Result<String, error::Error>
+    where
+        Self: Sized,
+    {
+        SpiderClient::new()?.fetch::<Self>(symbol, period)
+    }
 }
@@ -0,0 +1,374 @@
+//! Versioned migrations of the PostgreSQL statement tables
+//!
+//! Every statement table goes through the same ordered migrations. The
+//! version each table is at is recorded in `finspider_migrations`, and
+//! pending migrations are applied in one transaction when the table is
+//! initialized. Tables created before versions were recorded start from
+//! version 0, which is why the migrations only change what is missing.
+//!
+//! Line items added to a statement need a migration of their own, as
+//! tables at the latest version are not changed again.
+
+use crate::database;
+use crate::error::Error;
+use crate::statement::FieldMeta;
+use crate::FinancialStatement;
+use postgres::{Client, GenericClient};
+
+/// Schema version of tables with every migration applied, which is also
+/// the version of newly created statements
+pub use crate::statement::SCHEMA_VERSION;
+
+/// Change to the layout of a statement table
+pub struct Migration {
+    /// Version of the table once applied
+    pub version: i16,
+    pub description: &'static str,
+    /// SQL applying the change to `table`, owned by `owner`, with the
+    /// line item columns `fields`
+    pub up: fn(table: &str, owner: &str, fields: &[FieldMeta]) -> String,
+}
+
+/// All the migrations, oldest first
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Statement table",
+        up: |table, owner, _| {
+            format!(
+                "
+CREATE TABLE IF NOT EXISTS {table} (
+        id serial NOT NULL,
+        symbol varchar(80) NOT NULL,
+        term date NOT NULL,
+        filed date NOT NULL,
+        hash text UNIQUE NOT NULL,
+        version smallint NOT NULL
+);
+ALTER TABLE {table} OWNER TO {owner};"
+            )
+        },
+    },
+    Migration {
+        version: 2,
+        description: "Line items stored as numeric instead of varchar",
+        up: |table, _, fields| {
+            let added: Vec<String> = fields
+                .iter()
+                .map(|field| format!("ADD COLUMN IF NOT EXISTS {} numeric", field.name))
+                .collect();
+
+            // Text as shown on the page, with thousands separators
+            let converted: Vec<String> = fields
+                .iter()
+                .map(|field| {
+                    format!(
+                        "ALTER COLUMN {0} TYPE numeric USING CASE WHEN {0}::text ~ '[0-9]'
+                             THEN regexp_replace({0}::text, '[^0-9.-]', '', 'g')::numeric END",
+                        field.name
+                    )
+                })
+                .collect();
+
+            format!(
+                "ALTER TABLE {table} {};\nALTER TABLE {table} {};",
+                added.join(", "),
+                converted.join(", ")
+            )
+        },
+    },
+    Migration {
+        version: 3,
+        description: "Period, kind and source of statements, and rows with unknown titles",
+        up: |table, _, _| {
+            format!(
+                "
+ALTER TABLE {table}
+        ADD COLUMN IF NOT EXISTS period varchar(20) NOT NULL DEFAULT 'annual',
+        ADD COLUMN IF NOT EXISTS kind varchar(20) NOT NULL DEFAULT 'reported',
+        ADD COLUMN IF NOT EXISTS source varchar(20) NOT NULL DEFAULT 'yahoo',
+        ADD COLUMN IF NOT EXISTS extra jsonb NOT NULL DEFAULT '{{}}';"
+            )
+        },
+    },
+    Migration {
+        version: 4,
+        description: "History of restated statements",
+        up: |table, owner, _| {
+            // Rows saved before revisions were tracked have always been current
+            format!(
+                "
+ALTER TABLE {table} ADD COLUMN IF NOT EXISTS valid_from timestamptz NOT NULL DEFAULT '-infinity';
+ALTER TABLE {table} ALTER COLUMN valid_from SET DEFAULT now();
+CREATE TABLE IF NOT EXISTS {table}_history (
+        LIKE {table},
+        valid_to timestamptz NOT NULL
+);
+ALTER TABLE {table}_history OWNER TO {owner};"
+            )
+        },
+    },
+    Migration {
+        version: 5,
+        description: "Filing statements were read from",
+        up: |table, _, _| {
+            format!(
+                "
+ALTER TABLE {table} ADD COLUMN IF NOT EXISTS filing jsonb;
+ALTER TABLE {table}_history ADD COLUMN IF NOT EXISTS filing jsonb;"
+            )
+        },
+    },
+    Migration {
+        version: 6,
+        description: "One current version of each term",
+        up: |table, _, fields| {
+            let fields: String = fields
+                .iter()
+                .map(|field| format!("{}, ", field.name))
+                .collect();
+            let columns = format!(
+                "id, symbol, term, period, kind, source, {fields}extra, filed, filing, hash, version, valid_from"
+            );
+
+            // Versions saved next to a newer one of their term were replaced by it
+            let older = format!(
+                "EXISTS (SELECT 1 FROM {table} AS newer
+                     WHERE (newer.symbol, newer.term, newer.period, newer.kind, newer.source)
+                         = ({table}.symbol, {table}.term, {table}.period, {table}.kind, {table}.source)
+                         AND newer.id > {table}.id)"
+            );
+            format!(
+                "
+INSERT INTO {table}_history ({columns}, valid_to)
+        SELECT {columns}, now() FROM {table} WHERE {older};
+DELETE FROM {table} WHERE {older};
+CREATE UNIQUE INDEX IF NOT EXISTS {table}_term ON {table} (symbol, term, period, kind, source);"
+            )
+        },
+    },
+];
+
+/// Version `table` is at, 0 when none was recorded
+pub fn version(client: &mut Client, table: &str) -> Result<i16, Error> {
+    create(client)?;
+    applied(client, table)
+}
+
+/// Recorded version of `table`. Tables dropped since start over.
+fn applied(client: &mut impl GenericClient, table: &str) -> Result<i16, Error> {
+    let row = client
+        .query_opt(
+            "SELECT version FROM finspider_migrations
+                 WHERE table_name = $1 AND to_regclass($1) IS NOT NULL",
+            &[&table],
+        )
+        .map_err(|_| Error::SQL)?;
+
+    Ok(row.map_or(0, |row| row.get("version")))
+}
+
+/// Apply the pending migrations to the table of `S` called `table`, owned
+/// by `owner`. Stored statements are upgraded to the new version along
+/// with the table, and hashed again as the hash covers the columns the
+/// migrations add.
+pub fn migrate<S: FinancialStatement>(
+    client: &mut Client,
+    table: &str,
+    owner: &str,
+) -> Result<(), Error> {
+    create(client)?;
+
+    let mut transaction = client.transaction().map_err(|_| Error::Transaction)?;
+
+    // Tables initialized at the same time wait for each other
+    transaction
+        .batch_execute("LOCK TABLE finspider_migrations IN EXCLUSIVE MODE;")
+        .map_err(|_| Error::Transaction)?;
+    let applied = applied(&mut transaction, table)?;
+
+    let pending: Vec<&Migration> = MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > applied)
+        .collect();
+    let Some(latest) = pending.last().map(|migration| migration.version) else {
+        return Ok(());
+    };
+
+    for migration in pending {
+        transaction
+            .batch_execute(&(migration.up)(table, owner, S::FIELDS))
+            .map_err(|_| Error::Migration {
+                table: table.to_string(),
+                version: migration.version,
+            })?;
+    }
+
+    rehash::<S>(&mut transaction, table, latest, true)?;
+    rehash::<S>(&mut transaction, &format!("{table}_history"), latest, false)?;
+
+    transaction
+        .execute(
+            "INSERT INTO finspider_migrations (table_name, version) VALUES ($1, $2)
+                 ON CONFLICT (table_name) DO UPDATE SET version = $2, applied = now()",
+            &[&table, &latest],
+        )
+        .map_err(|_| Error::SQL)?;
+
+    transaction.commit().map_err(|_| Error::Transaction)
+}
+
+/// Upgrade the statements of `table` older than `version` to it, with the
+/// hash of the statement they now hold. When hashes are `unique`,
+/// statements hashing the same as another one stored are duplicates and
+/// are dropped.
+fn rehash<S: FinancialStatement>(
+    client: &mut impl GenericClient,
+    table: &str,
+    version: i16,
+    unique: bool,
+) -> Result<(), Error> {
+    let sql = format!("SELECT * FROM {table} WHERE version < $1 ORDER BY id");
+    let rows = client.query(&sql, &[&version]).map_err(|_| Error::SQL)?;
+    let taken = format!("SELECT 1 FROM {table} WHERE hash = $1 AND id <> $2");
+    let update = format!("UPDATE {table} SET hash = $1, version = $2 WHERE id = $3 AND hash = $4");
+    let delete = format!("DELETE FROM {table} WHERE id = $1 AND hash = $2");
+
+    for row in rows {
+        let statement: S = database::statement(&row)?;
+        let (id, stored): (i32, String) = (row.get("id"), row.get("hash"));
+        let hash = statement.hash();
+
+        if unique
+            && !client
+                .query(&taken, &[&hash, &id])
+                .map_err(|_| Error::SQL)?
+                .is_empty()
+        {
+            client
+                .execute(&delete, &[&id, &stored])
+                .map_err(|_| Error::SQL)?;
+        } else {
+            client
+                .execute(&update, &[&hash, &version, &id, &stored])
+                .map_err(|_| Error::SQL)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create the table of applied versions
+fn create(client: &mut Client) -> Result<(), Error> {
+    client
+        .batch_execute(
+            "
+CREATE TABLE IF NOT EXISTS finspider_migrations (
+        table_name text PRIMARY KEY,
+        version smallint NOT NULL,
+        applied timestamptz NOT NULL DEFAULT now()
+);",
+        )
+        .map_err(|_| Error::TableCreation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cash_flows::database::CashFlowDB;
+    use crate::cash_flows::CashFlow;
+    use crate::store::{OnConflict, Saved};
+    use crate::{Decimal, FinancialStatement, Period, Source, TermKind};
+    use chrono::NaiveDate;
+    use postgres::NoTls;
+    use std::env;
+
+    const TABLE: &str = "cash_flows_test_migrations";
+
+    /// Test migrating a table created by the first versions
+    #[test]
+    fn test_migrations() {
+        let db_user = env::var("DB_USER").expect("DB_USER not set");
+        let port = env::var("DB_PORT").expect("DB_PORT not set");
+        let password = env::var("DB_PASSWORD").expect("DB_PASSWORD not set");
+        let host = env::var("DB_HOST").expect("DB_HOST not set");
+        let db_name = env::var("DB_NAME").expect("DB_NAME not set");
+        let db_url = format!("postgres://{db_user}:{password}@{host}:{port}/{db_name}");
+        let mut db = Client::connect(&db_url, NoTls).unwrap();
+
+        assert_eq!(MIGRATIONS.last().unwrap().version, SCHEMA_VERSION);
+        assert_eq!(CashFlow::SCHEMA_VERSION, SCHEMA_VERSION);
+
+        let sql = format!(
+            "
+DROP TABLE IF EXISTS {TABLE}, {TABLE}_history;
+DELETE FROM finspider_migrations WHERE table_name = '{TABLE}';
+CREATE TABLE {TABLE} (
+        id serial NOT NULL,
+        symbol varchar(80) NOT NULL,
+        term date NOT NULL,
+        operating_cash_flow varchar(100),
+        capital_expenditure varchar(100),
+        free_cash_flow varchar(100),
+        filed date NOT NULL,
+        hash text UNIQUE NOT NULL,
+        version smallint NOT NULL
+);
+INSERT INTO {TABLE} (symbol, term, operating_cash_flow, capital_expenditure, free_cash_flow, filed, hash, version)
+        VALUES ('SBKP.JO', '2023-12-31', '48,000,000', '-6,339,000', '-', '2024-02-01', 'replaced', 0),
+               ('SBKP.JO', '2023-12-31', '48,698,000', '-6,339,000', '-', '2024-03-01', 'old', 0);"
+        );
+        create(&mut db).unwrap();
+        db.batch_execute(&sql).unwrap();
+        assert_eq!(version(&mut db, TABLE).unwrap(), 0);
+
+        CashFlowDB::init(&mut db, TABLE, &db_user).unwrap();
+        assert_eq!(version(&mut db, TABLE).unwrap(), SCHEMA_VERSION);
+
+        let cash_flows = CashFlowDB::read_all(&mut db, TABLE).unwrap();
+        assert_eq!(cash_flows.len(), 1);
+        assert_eq!(cash_flows[0].period, Period::Annual);
+        assert_eq!(cash_flows[0].kind, TermKind::Reported);
+        assert_eq!(cash_flows[0].source, Source::Yahoo);
+        assert_eq!(cash_flows[0].version, SCHEMA_VERSION);
+        assert_eq!(
+            cash_flows[0].operating_cash_flow,
+            Some(Decimal::new(48698000, 0))
+        );
+        assert_eq!(
+            cash_flows[0].capital_expenditure,
+            Some(Decimal::new(-6339000, 0))
+        );
+        assert_eq!(cash_flows[0].free_cash_flow, None);
+
+        // Versions saved next to a newer one of their term were replaced
+        let sql = format!("SELECT operating_cash_flow FROM {TABLE}_history");
+        let replaced: Vec<Decimal> = db
+            .query(&sql, &[])
+            .unwrap()
+            .iter()
+            .map(|row| row.get("operating_cash_flow"))
+            .collect();
+        assert_eq!(replaced, vec![Decimal::new(48000000, 0)]);
+
+        // Migrated tables take new statements, and are left as they are
+        let mut cash_flow = cash_flows[0].clone();
+        cash_flow.free_cash_flow = Some(Decimal::ONE);
+        assert!(CashFlowDB::save(&mut db, TABLE, cash_flow.clone()).is_err());
+        cash_flow.term = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+        CashFlowDB::save(&mut db, TABLE, cash_flow).unwrap();
+        CashFlowDB::init(&mut db, TABLE, &db_user).unwrap();
+        migrate::<CashFlow>(&mut db, TABLE, &db_user).unwrap();
+        assert_eq!(CashFlowDB::read_all(&mut db, TABLE).unwrap().len(), 2);
+
+        // Migrated statements are hashed like new ones, so saving them
+        // again changes nothing
+        let sql = format!("SELECT hash FROM {TABLE} ORDER BY id LIMIT 1");
+        let stored: String = db.query_one(&sql, &[]).unwrap().get("hash");
+        assert_eq!(stored, cash_flows[0].hash());
+        assert_eq!(
+            CashFlowDB::upsert(&mut db, TABLE, cash_flows[0].clone(), OnConflict::Update).unwrap(),
+            Saved::Unchanged
+        );
+    }
+}
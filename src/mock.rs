@@ -0,0 +1,98 @@
+//! Minimal HTTP server used by the tests
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+/// Canned response returned by [`MockServer`]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl MockResponse {
+    pub fn ok(body: &str) -> Self {
+        MockResponse::status(200, body)
+    }
+
+    pub fn status(status: u16, body: &str) -> Self {
+        MockResponse {
+            status,
+            headers: vec![],
+            body: body.to_string(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Serves one response per connection, in order, then shuts down
+pub struct MockServer {
+    pub url: String,
+    handle: thread::JoinHandle<Vec<Vec<String>>>,
+}
+
+impl MockServer {
+    pub fn start(responses: Vec<MockResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut head = vec![];
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    head.push(line.to_string());
+                }
+
+                // The body, if any, is kept as the last line
+                let length = head
+                    .iter()
+                    .find_map(|line| line.strip_prefix("content-length: "))
+                    .map_or(0, |length| length.parse().unwrap());
+                if length > 0 {
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+                    head.push(String::from_utf8(body).unwrap());
+                }
+
+                let mut reply = format!(
+                    "HTTP/1.1 {} MOCK\r\nContent-Length: {}\r\nConnection: close\r\n",
+                    response.status,
+                    response.body.len()
+                );
+                for (name, value) in &response.headers {
+                    reply.push_str(&format!("{name}: {value}\r\n"));
+                }
+                reply.push_str("\r\n");
+                reply.push_str(&response.body);
+
+                stream.write_all(reply.as_bytes()).unwrap();
+                requests.push(head);
+            }
+
+            requests
+        });
+
+        MockServer { url, handle }
+    }
+
+    /// Wait for all responses to be served and return the request heads
+    pub fn requests(self) -> Vec<Vec<String>> {
+        self.handle.join().unwrap()
+    }
+}
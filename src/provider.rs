@@ -0,0 +1,127 @@
+//! Sources of financial statements
+//!
+//! A [`Provider`] hides where and how statements are downloaded. Code
+//! written against it works with Yahoo, Investing.com, SEC EDGAR or any
+//! other source, such as an internal service or a local stand-in for
+//! tests.
+
+use crate::balance_sheets::BalanceSheet;
+use crate::cash_flows::CashFlow;
+use crate::error::Error;
+use crate::income_statements::IncomeStatement;
+use crate::{Period, Source};
+
+pub use crate::edgar::EdgarProvider;
+pub use crate::investing::InvestingProvider;
+pub use crate::yahoo::YahooProvider;
+
+/// Downloads typed financial statements. Providers can be picked at
+/// runtime as `Box<dyn Provider>`, and
+/// [`FinancialStatement::download`](crate::FinancialStatement::download)
+/// downloads any kind of statement from one.
+pub trait Provider {
+    /// Source recorded on every statement from this provider
+    fn source(&self) -> Source;
+
+    /// Download and parse the `period` balance sheets for `symbol`
+    fn balance_sheets(&self, symbol: &str, period: Period) -> Result<Vec<BalanceSheet>, Error>;
+
+    /// Download and parse the `period` cash flows for `symbol`
+    fn cash_flows(&self, symbol: &str, period: Period) -> Result<Vec<CashFlow>, Error>;
+
+    /// Download and parse the `period` income statements for `symbol`
+    fn income_statements(
+        &self,
+        symbol: &str,
+        period: Period,
+    ) -> Result<Vec<IncomeStatement>, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Decimal, FinancialStatement, SpiderClient, TermKind};
+    use chrono::NaiveDate;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+    /// Stand-in serving the same made up statement for every symbol
+    struct Local;
+
+    impl Local {
+        fn statements<S: FinancialStatement>(&self, symbol: &str, period: Period) -> Vec<S> {
+            let term = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+            let mut statement = S::new(symbol, term, period, TermKind::Reported, self.source());
+
+            if let Some(field) = S::FIELDS.first() {
+                statement.insert(field.name, Some(Decimal::ONE_HUNDRED));
+            }
+            statement.insert("Local Row", Some(Decimal::ONE));
+
+            vec![statement]
+        }
+    }
+
+    impl Provider for Local {
+        fn source(&self) -> Source {
+            Source::Other("local".to_string())
+        }
+
+        fn balance_sheets(&self, symbol: &str, period: Period) -> Result<Vec<BalanceSheet>, Error> {
+            Ok(self.statements(symbol, period))
+        }
+
+        fn cash_flows(&self, symbol: &str, period: Period) -> Result<Vec<CashFlow>, Error> {
+            Ok(self.statements(symbol, period))
+        }
+
+        fn income_statements(
+            &self,
+            symbol: &str,
+            period: Period,
+        ) -> Result<Vec<IncomeStatement>, Error> {
+            Ok(self.statements(symbol, period))
+        }
+    }
+
+    fn latest<S: FinancialStatement>(provider: &dyn Provider, symbol: &str) -> S {
+        let mut statements = S::download(provider, symbol, Period::Annual).unwrap();
+        assert!(statements
+            .iter()
+            .all(|statement| *statement.source() == provider.source()));
+        statements.remove(0)
+    }
+
+    #[test]
+    fn test_providers() {
+        let local: BalanceSheet = latest(&Local, "SBKP.JO");
+        assert_eq!(local.source, Source::Other("local".to_string()));
+        assert_eq!(local.total_assets, Some(Decimal::ONE_HUNDRED));
+        assert_eq!(local.field("Local Row"), Some(Decimal::ONE));
+        assert_eq!(local.version, BalanceSheet::SCHEMA_VERSION);
+
+        let yahoo = YahooProvider::new(
+            SpiderClient::builder()
+                .replay(format!("{FIXTURES}/yahoo"))
+                .build()
+                .unwrap(),
+        );
+        let balance_sheet: BalanceSheet = latest(&yahoo, "SBKP.JO");
+        assert_eq!(balance_sheet.term, local.term);
+
+        let investing = InvestingProvider::new(
+            SpiderClient::builder()
+                .replay(format!("{FIXTURES}/investing"))
+                .build()
+                .unwrap(),
+        )
+        .pair_id("SBKP.JO", "41213");
+
+        // Chosen at runtime
+        let providers: Vec<Box<dyn Provider>> = vec![Box::new(yahoo), Box::new(investing)];
+        let yahoo = providers[0].cash_flows("SBKP.JO", Period::Annual).unwrap();
+        let investing: CashFlow = latest(providers[1].as_ref(), "SBKP.JO");
+        assert_eq!(investing.term, yahoo[1].term);
+        assert_eq!(investing.operating_cash_flow, yahoo[1].operating_cash_flow);
+    }
+}
@@ -0,0 +1,145 @@
+//! Per-host throttling of downloads
+//!
+//! Every host gets a token bucket refilled at a fixed rate. Clones of a
+//! [`RateLimiter`] share their buckets, so all clients built with the
+//! same limiter (or the default one) throttle each other.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default sustained number of requests per second to a host
+pub const DEFAULT_REQUESTS_PER_SECOND: f64 = 2.0;
+/// Default number of requests that may be sent to a host at once
+pub const DEFAULT_BURST: u32 = 5;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token bucket rate limiter keyed by host
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: u32,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl Default for RateLimiter {
+    /// The limiter shared by every client that is not given its own
+    fn default() -> Self {
+        static SHARED: OnceLock<RateLimiter> = OnceLock::new();
+        SHARED
+            .get_or_init(|| RateLimiter::new(DEFAULT_REQUESTS_PER_SECOND, DEFAULT_BURST))
+            .clone()
+    }
+}
+
+impl RateLimiter {
+    /// Allow `requests_per_second` to every host, with up to `burst`
+    /// requests at once
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        RateLimiter {
+            requests_per_second,
+            burst: burst.max(1),
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Never wait
+    pub fn unlimited() -> Self {
+        RateLimiter::new(f64::INFINITY, 1)
+    }
+
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Take a token for the host of `url` and return how long to wait
+    /// before sending the request
+    pub fn reserve(&self, url: &str) -> Duration {
+        if !self.requests_per_second.is_finite() || self.requests_per_second <= 0.0 {
+            return Duration::ZERO;
+        }
+
+        let now = Instant::now();
+        let burst = f64::from(self.burst);
+        let mut buckets = self.buckets.lock().unwrap_or_else(|err| err.into_inner());
+        let bucket = buckets.entry(host(url)).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(burst);
+        bucket.updated = now;
+
+        // Tokens may go negative, queueing the request behind earlier ones
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.requests_per_second)
+        }
+    }
+}
+
+/// Host and port of `url`, or the whole url if it cannot be parsed
+fn host(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            _ => url.to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(10.0, 2);
+        let shared = limiter.clone();
+
+        assert_eq!(
+            limiter.reserve("https://finance.yahoo.com/a"),
+            Duration::ZERO
+        );
+        assert_eq!(
+            shared.reserve("https://finance.yahoo.com/b"),
+            Duration::ZERO
+        );
+
+        // The burst is used up, wait for the next token
+        let wait = limiter.reserve("https://finance.yahoo.com/c");
+        assert!(wait > Duration::from_millis(50) && wait <= Duration::from_millis(100));
+        let wait = shared.reserve("https://finance.yahoo.com/d");
+        assert!(wait > Duration::from_millis(150) && wait <= Duration::from_millis(200));
+
+        // Other hosts have their own bucket
+        assert_eq!(
+            limiter.reserve("https://www.investing.com/a"),
+            Duration::ZERO
+        );
+        assert_eq!(limiter.reserve("http://127.0.0.1:8080/a"), Duration::ZERO);
+
+        let unlimited = RateLimiter::unlimited();
+        for _ in 0..10 {
+            assert_eq!(
+                unlimited.reserve("https://finance.yahoo.com/a"),
+                Duration::ZERO
+            );
+        }
+    }
+}
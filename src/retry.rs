@@ -0,0 +1,220 @@
+//! HTTP status handling and retrying of failed downloads
+
+use crate::error::Error;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+
+/// How often and how long to wait before trying a failed download
+/// again. Only rate limiting, server errors, timeouts and connection
+/// failures are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        RetryPolicy::default().max_retries(0)
+    }
+
+    /// Number of retries after the first attempt
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Delay before the first retry, doubled for every following one
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Upper bound of the exponential backoff and of any wait asked for
+    /// with `Retry-After`
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Whether `err` on the zero based `attempt` should be retried.
+    /// Servers asking to wait longer than the maximum delay are given up on.
+    pub fn should_retry(&self, attempt: u32, err: &Error) -> bool {
+        attempt < self.max_retries
+            && err.is_retryable()
+            && retry_after(err).is_none_or(|retry_after| retry_after <= self.max_delay)
+    }
+
+    /// Time to wait after `err` on the zero based `attempt`. A
+    /// `Retry-After` sent by the server is honoured up to the maximum
+    /// delay, otherwise the backoff is jittered between half and all of
+    /// the exponential delay.
+    pub fn delay(&self, attempt: u32, err: &Error) -> Duration {
+        if let Some(retry_after) = retry_after(err) {
+            return retry_after.min(self.max_delay);
+        }
+
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+
+        backoff / 2 + backoff.mul_f64(jitter() / 2.0)
+    }
+}
+
+/// Wait asked for by the server that sent `err`
+fn retry_after(err: &Error) -> Option<Duration> {
+    match err {
+        Error::RateLimited { retry_after, .. } => *retry_after,
+        _ => None,
+    }
+}
+
+/// Random number in `0.0..1.0`
+fn jitter() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+
+    if let Ok(now) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        hasher.write_u128(now.as_nanos());
+    }
+
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Turn unsuccessful responses into errors. `final_url` is the url
+/// after following redirects.
+pub(crate) fn check_status(
+    url: &str,
+    final_url: &reqwest::Url,
+    status: StatusCode,
+    headers: &HeaderMap,
+) -> Result<(), Error> {
+    // Yahoo redirects unknown symbols to the symbol lookup page
+    if final_url.path().starts_with("/lookup") {
+        return Err(Error::NotFound(url.to_string()));
+    }
+
+    match status {
+        status if status.is_success() => Ok(()),
+        StatusCode::NOT_FOUND => Err(Error::NotFound(url.to_string())),
+        StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited {
+            url: url.to_string(),
+            retry_after: headers
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after),
+        }),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(Error::Blocked(url.to_string())),
+        status => Err(Error::Status {
+            url: url.to_string(),
+            status: status.as_u16(),
+        }),
+    }
+}
+
+/// Parse a `Retry-After` header, given either in seconds or as an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    let wait = date.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::SpiderClient;
+
+    #[test]
+    fn test_retry() {
+        let policy = RetryPolicy::default()
+            .max_retries(2)
+            .base_delay(Duration::from_millis(1));
+
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert!(policy.delay(1, &Error::NotFound(String::new())) <= Duration::from_millis(2));
+
+        // Waits longer than the maximum delay are not retried
+        let day = Error::RateLimited {
+            url: String::new(),
+            retry_after: Some(Duration::from_secs(86400)),
+        };
+        assert!(!policy.should_retry(0, &day));
+        assert_eq!(policy.delay(0, &day), Duration::from_secs(30));
+
+        // Rate limited, then a server error, then success
+        let server = MockServer::start(vec![
+            MockResponse::status(429, "").header("Retry-After", "0"),
+            MockResponse::status(503, ""),
+            MockResponse::ok("<html></html>"),
+        ]);
+        let client = SpiderClient::builder().retry(policy).build().unwrap();
+        let url = format!("{}/quote/SBKP.JO/balance-sheet", server.url);
+
+        assert_eq!(client.get(&url).unwrap(), "<html></html>");
+        assert_eq!(server.requests().len(), 3);
+
+        // Give up once the retries are used up
+        let server = MockServer::start(vec![
+            MockResponse::status(500, ""),
+            MockResponse::status(500, ""),
+            MockResponse::status(500, ""),
+        ]);
+        let url = format!("{}/quote/SBKP.JO/balance-sheet", server.url);
+
+        assert!(matches!(
+            client.get(&url),
+            Err(Error::Status { status: 500, .. })
+        ));
+
+        // Not retried
+        let server = MockServer::start(vec![
+            MockResponse::status(404, ""),
+            MockResponse::status(403, ""),
+        ]);
+        let url = format!("{}/quote/NOPE/balance-sheet", server.url);
+
+        assert!(matches!(client.get(&url), Err(Error::NotFound(_))));
+        assert!(matches!(client.get(&url), Err(Error::Blocked(_))));
+        server.requests();
+
+        // Given up on at once when asked to wait for a day
+        let server = MockServer::start(vec![
+            MockResponse::status(429, "").header("Retry-After", "86400")
+        ]);
+        let url = format!("{}/quote/SBKP.JO/balance-sheet", server.url);
+
+        assert!(matches!(
+            client.get(&url),
+            Err(Error::RateLimited {
+                retry_after: Some(_),
+                ..
+            })
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+}
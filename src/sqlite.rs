@@ -0,0 +1,513 @@
+//! SQLite storage shared by all statement types
+//!
+//! Tables have the same columns as the PostgreSQL ones, so a whole cache
+//! fits in a single file. SQLite has no exact numeric type, values are
+//! stored as text to keep every digit and unknown rows as JSON text.
+//! Times are stored as RFC 3339 text in UTC, which sorts like the times
+//! themselves.
+
+use crate::error::Error;
+use crate::statement;
+use crate::store::{OnConflict, Saved};
+use crate::{Decimal, FinancialStatement};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row, ToSql};
+
+/// Create the table of `S` called `table` and its history, which keeps
+/// the versions of statements replaced by restatements
+pub(crate) fn init<S: FinancialStatement>(conn: &Connection, table: &str) -> Result<(), Error> {
+    let fields: String = S::FIELDS
+        .iter()
+        .map(|field| format!("\n    {} text,", field.name))
+        .collect();
+    let sql = format!(
+        "CREATE TABLE IF NOT EXISTS {table} (
+    id integer PRIMARY KEY,
+    symbol text NOT NULL,
+    term text NOT NULL,
+    period text NOT NULL,
+    kind text NOT NULL,
+    source text NOT NULL,{fields}
+    extra text NOT NULL DEFAULT '{{}}',
+    filed text NOT NULL,
+    filing text,
+    hash text UNIQUE NOT NULL,
+    version integer NOT NULL,
+    valid_from text NOT NULL DEFAULT ''
+);
+CREATE TABLE IF NOT EXISTS {table}_history AS SELECT *, NULL AS valid_to FROM {table} WHERE 0;"
+    );
+    conn.execute_batch(&sql).map_err(|_| Error::TableCreation)?;
+
+    // Tables created by older versions lack the newer line items, and
+    // rows saved before revisions were tracked have always been current
+    let added: Vec<(&str, &str)> = S::FIELDS
+        .iter()
+        .map(|field| (field.name, "text"))
+        .chain([
+            ("filing", "text"),
+            ("valid_from", "text NOT NULL DEFAULT ''"),
+        ])
+        .collect();
+    for table in [table.to_string(), format!("{table}_history")] {
+        let existing = columns(conn, &table)?;
+        for (name, kind) in added
+            .iter()
+            .filter(|(name, _)| !existing.iter().any(|column| column == name))
+        {
+            conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {name} {kind};"))
+                .map_err(|_| Error::TableCreation)?;
+        }
+    }
+
+    // Only one version of a term is current, older ones saved next to a
+    // newer one before this was enforced were replaced by it
+    let columns = statement::columns::<S>().join(", ");
+    let older = format!(
+        "EXISTS (SELECT 1 FROM {table} AS newer
+             WHERE (newer.symbol, newer.term, newer.period, newer.kind, newer.source)
+                 = ({table}.symbol, {table}.term, {table}.period, {table}.kind, {table}.source)
+                 AND newer.id > {table}.id)"
+    );
+    let sql = format!(
+        "BEGIN;
+INSERT INTO {table}_history (id, {columns}, valid_from, valid_to)
+    SELECT id, {columns}, valid_from, '{}' FROM {table} WHERE {older};
+DELETE FROM {table} WHERE {older};
+CREATE UNIQUE INDEX IF NOT EXISTS {table}_term ON {table} (symbol, term, period, kind, source);
+COMMIT;",
+        time(Utc::now())
+    );
+    conn.execute_batch(&sql).map_err(|_| Error::TableCreation)
+}
+
+/// Names of the columns of `table`
+fn columns(conn: &Connection, table: &str) -> Result<Vec<String>, Error> {
+    let mut query = conn
+        .prepare(&format!("PRAGMA table_info({table})"))
+        .map_err(|_| Error::TableCreation)?;
+    let names = query
+        .query_map([], |row| row.get("name"))
+        .map_err(|_| Error::TableCreation)?;
+
+    names
+        .collect::<Result<_, _>>()
+        .map_err(|_| Error::TableCreation)
+}
+
+/// Insert `statement` into `table`, failing if a version of its term is
+/// stored. Restatements go through [`revise`].
+pub(crate) fn save<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+    statement: &S,
+) -> Result<(), Error> {
+    insert(conn, table, statement, Utc::now(), "")?;
+    Ok(())
+}
+
+/// Insert `statement` into `table`, doing `on_conflict` when one with
+/// the same hash is stored. Fails if another version of its term is
+/// stored, like [`save`].
+pub(crate) fn upsert<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+    statement: &S,
+    on_conflict: OnConflict,
+) -> Result<Saved, Error> {
+    if insert(
+        conn,
+        table,
+        statement,
+        Utc::now(),
+        " ON CONFLICT (hash) DO NOTHING",
+    )? > 0
+    {
+        return Ok(Saved::Inserted);
+    }
+    if on_conflict == OnConflict::Skip {
+        return Ok(Saved::Unchanged);
+    }
+
+    let sql = format!(
+        "UPDATE {table} SET filed = ?1, filing = ?2, version = ?3
+             WHERE hash = ?4 AND (filed IS NOT ?1 OR filing IS NOT ?2 OR version IS NOT ?3);"
+    );
+    let updated = conn
+        .execute(
+            &sql,
+            (
+                statement.filed().to_string(),
+                filing(statement)?,
+                statement.version(),
+                statement.hash(),
+            ),
+        )
+        .map_err(|_| Error::SQL)?;
+
+    Ok(if updated > 0 {
+        Saved::Updated
+    } else {
+        Saved::Unchanged
+    })
+}
+
+/// Make `statement` the current version of its term from `at`. Other
+/// versions of the term, from the same source and of the same kind, move
+/// to the history.
+pub(crate) fn revise<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+    statement: &S,
+    at: DateTime<Utc>,
+) -> Result<Saved, Error> {
+    let transaction = conn
+        .unchecked_transaction()
+        .map_err(|_| Error::Transaction)?;
+
+    let sql = format!("SELECT EXISTS (SELECT 1 FROM {table} WHERE hash = ?1)");
+    let stored: bool = transaction
+        .query_row(&sql, [statement.hash()], |row| row.get(0))
+        .map_err(|_| Error::SQL)?;
+    if stored {
+        return Ok(Saved::Unchanged);
+    }
+
+    let columns = statement::columns::<S>().join(", ");
+    let same_term = "symbol = ?1 AND term = ?2 AND period = ?3 AND kind = ?4 AND source = ?5";
+    let term = (
+        statement.symbol(),
+        statement.term().to_string(),
+        statement.period().as_str(),
+        statement.kind().as_str(),
+        statement.source().as_str(),
+    );
+    let sql = format!(
+        "INSERT INTO {table}_history (id, {columns}, valid_from, valid_to)
+             SELECT id, {columns}, valid_from, ?6 FROM {table} WHERE {same_term};"
+    );
+    let replaced = transaction
+        .execute(&sql, (term.0, &term.1, term.2, term.3, term.4, time(at)))
+        .map_err(|_| Error::SQL)?;
+
+    let sql = format!("DELETE FROM {table} WHERE {same_term};");
+    transaction.execute(&sql, term).map_err(|_| Error::SQL)?;
+
+    insert(&transaction, table, statement, at, "")?;
+    transaction.commit().map_err(|_| Error::Transaction)?;
+
+    Ok(if replaced > 0 {
+        Saved::Restated
+    } else {
+        Saved::Inserted
+    })
+}
+
+/// Insert `statement` into `table`, current from `valid_from` and
+/// followed by the `conflict` clause
+fn insert<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+    statement: &S,
+    valid_from: DateTime<Utc>,
+    conflict: &str,
+) -> Result<usize, Error> {
+    let mut names = statement::columns::<S>();
+    names.push("valid_from");
+    let placeholders: Vec<String> = (1..=names.len()).map(|n| format!("?{n}")).collect();
+    let sql = format!(
+        "INSERT INTO {table} ({}) VALUES ({}){conflict};",
+        names.join(", "),
+        placeholders.join(", ")
+    );
+
+    let text = |text: &str| Value::Text(text.to_string());
+    let extra = serde_json::to_string(statement.extra()).map_err(|_| Error::Field)?;
+    let values: Vec<Value> = [
+        text(statement.symbol()),
+        text(&statement.term().to_string()),
+        text(statement.period().as_str()),
+        text(statement.kind().as_str()),
+        text(statement.source().as_str()),
+    ]
+    .into_iter()
+    .chain(
+        statement
+            .fields()
+            .into_iter()
+            .map(|(_, value)| value.map_or(Value::Null, |value| text(&value.to_string()))),
+    )
+    .chain([
+        Value::Text(extra),
+        text(&statement.filed().to_string()),
+        filing(statement)?.map_or(Value::Null, Value::Text),
+        Value::Text(statement.hash()),
+        Value::Integer(statement.version().into()),
+        Value::Text(time(valid_from)),
+    ])
+    .collect();
+
+    conn.execute(&sql, params_from_iter(values))
+        .map_err(|_| Error::SQL)
+}
+
+/// Filing of `statement` as stored, in JSON
+fn filing<S: FinancialStatement>(statement: &S) -> Result<Option<String>, Error> {
+    statement
+        .filing()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|_| Error::Field)
+}
+
+/// Statement of `table` identified by `hash`
+pub(crate) fn read<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+    hash: &str,
+) -> Result<Option<S>, Error> {
+    let sql = format!("SELECT * FROM {table} WHERE hash = ?1");
+    Ok(query(conn, &sql, &[&hash])?.into_iter().next())
+}
+
+/// Every statement of `table`
+pub(crate) fn read_all<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+) -> Result<Vec<S>, Error> {
+    let sql = format!("SELECT * FROM {table}");
+    query(conn, &sql, &[])
+}
+
+/// Every statement of `table` for `symbol`
+pub(crate) fn read_all_by_symbol<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+    symbol: &str,
+) -> Result<Vec<S>, Error> {
+    let sql = format!("SELECT * FROM {table} WHERE symbol = ?1");
+    query(conn, &sql, &[&symbol])
+}
+
+/// Statements of `table` for `symbol` with a term from `from` to `to`,
+/// oldest first. Dates are stored as ISO 8601 text, which sorts like
+/// the dates themselves.
+pub(crate) fn read_range<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+    symbol: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<S>, Error> {
+    let sql =
+        format!("SELECT * FROM {table} WHERE symbol = ?1 AND term BETWEEN ?2 AND ?3 ORDER BY term");
+    query(conn, &sql, &[&symbol, &from.to_string(), &to.to_string()])
+}
+
+/// Delete the statement of `table` identified by `hash`, returning
+/// whether it was stored
+pub(crate) fn delete(conn: &Connection, table: &str, hash: &str) -> Result<bool, Error> {
+    let sql = format!("DELETE FROM {table} WHERE hash = ?1");
+    let deleted = conn.execute(&sql, [hash]).map_err(|_| Error::SQL)?;
+
+    Ok(deleted > 0)
+}
+
+/// Statements of `table` for `symbol` with a term from `from` to `to`
+/// as they were stored at `at`, oldest first
+pub(crate) fn read_as_of<S: FinancialStatement>(
+    conn: &Connection,
+    table: &str,
+    symbol: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    at: DateTime<Utc>,
+) -> Result<Vec<S>, Error> {
+    let columns = statement::columns::<S>().join(", ");
+    let sql = format!(
+        "SELECT {columns} FROM {table}
+             WHERE symbol = ?1 AND term BETWEEN ?2 AND ?3 AND valid_from <= ?4
+         UNION ALL
+         SELECT {columns} FROM {table}_history
+             WHERE symbol = ?1 AND term BETWEEN ?2 AND ?3 AND valid_from <= ?4 AND valid_to > ?4
+         ORDER BY term"
+    );
+    query(
+        conn,
+        &sql,
+        &[&symbol, &from.to_string(), &to.to_string(), &time(at)],
+    )
+}
+
+/// `time` as stored in the tables
+fn time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn query<S: FinancialStatement>(
+    conn: &Connection,
+    sql: &str,
+    params: &[&dyn ToSql],
+) -> Result<Vec<S>, Error> {
+    let mut query = conn.prepare(sql).map_err(|_| Error::SQL)?;
+    let mut rows = query.query(params).map_err(|_| Error::SQL)?;
+    let mut statements = vec![];
+
+    while let Some(row) = rows.next().map_err(|_| Error::SQL)? {
+        statements.push(statement(row)?);
+    }
+
+    Ok(statements)
+}
+
+/// Statement stored in `row`
+fn statement<S: FinancialStatement>(row: &Row) -> Result<S, Error> {
+    let text = |name: &str| row.get::<_, String>(name).map_err(|_| Error::SQL);
+    let date = |name: &str| text(name)?.parse::<NaiveDate>().map_err(|_| Error::Field);
+
+    let mut statement = S::new(
+        &text("symbol")?,
+        date("term")?,
+        text("period")?.parse()?,
+        text("kind")?.parse()?,
+        text("source")?.parse()?,
+    );
+
+    for field in S::FIELDS {
+        let value: Option<String> = row.get(field.name).map_err(|_| Error::SQL)?;
+        let value = value
+            .map(|value| value.parse::<Decimal>())
+            .transpose()
+            .map_err(|_| Error::Field)?;
+        statement.insert(field.name, value);
+    }
+
+    *statement.extra_mut() = serde_json::from_str(&text("extra")?).map_err(|_| Error::Field)?;
+    *statement.filed_mut() = date("filed")?;
+    let filing: Option<String> = row.get("filing").map_err(|_| Error::SQL)?;
+    *statement.filing_mut() = filing
+        .map(|filing| serde_json::from_str(&filing))
+        .transpose()
+        .map_err(|_| Error::Field)?;
+    *statement.version_mut() = row.get("version").map_err(|_| Error::SQL)?;
+
+    Ok(statement)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::balance_sheets::sqlite::BalanceSheetsSqlite;
+    use crate::balance_sheets::BalanceSheet;
+    use crate::cash_flows::sqlite::CashFlowSqlite;
+    use crate::cash_flows::CashFlow;
+    use crate::income_statements::sqlite::IncomeStatementsSqlite;
+    use crate::income_statements::IncomeStatement;
+    use crate::statement::Filing;
+    use crate::{Decimal, FinancialStatement, Period, Source, TermKind};
+    use chrono::NaiveDate;
+    use rusqlite::Connection;
+    use std::{env, fs};
+
+    fn statement<S: FinancialStatement>(symbol: &str) -> S {
+        let term = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+        let mut statement = S::new(
+            symbol,
+            term,
+            Period::Quarterly,
+            TermKind::Ttm,
+            Source::Edgar,
+        );
+
+        for field in S::FIELDS.iter().step_by(2) {
+            statement.insert(field.name, Some(Decimal::new(-123456789, 3)));
+        }
+        statement.insert("Unknown Row", Some(Decimal::new(100000, 2)));
+        statement.insert("Empty Row", None);
+        *statement.filing_mut() = Some(Filing {
+            fiscal_year: Some(2023),
+            fiscal_period: Some("FY".to_string()),
+            accession: None,
+        });
+        *statement.version_mut() = 0;
+
+        statement
+    }
+
+    /// Test the routines for all three statement types in one file
+    #[test]
+    fn test_sqlite() {
+        let path = env::temp_dir().join(format!("finspider_test_{}.sqlite", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let balance_sheet: BalanceSheet = statement("SBKP.JO");
+        let cash_flow: CashFlow = statement("SBKP.JO");
+        let income_statement: IncomeStatement = statement("SBKP.JO");
+
+        {
+            let conn = Connection::open(&path).unwrap();
+            BalanceSheetsSqlite::init(&conn, "balance_sheets").unwrap();
+            CashFlowSqlite::init(&conn, "cash_flows").unwrap();
+            IncomeStatementsSqlite::init(&conn, "income_statements").unwrap();
+
+            BalanceSheetsSqlite::save(&conn, "balance_sheets", balance_sheet.clone()).unwrap();
+            CashFlowSqlite::save(&conn, "cash_flows", cash_flow.clone()).unwrap();
+            CashFlowSqlite::save(&conn, "cash_flows", statement("AAPL")).unwrap();
+            IncomeStatementsSqlite::save(&conn, "income_statements", income_statement.clone())
+                .unwrap();
+
+            // Hashes are unique, and so are terms
+            assert!(CashFlowSqlite::save(&conn, "cash_flows", cash_flow.clone()).is_err());
+            let mut restated = cash_flow.clone();
+            restated.free_cash_flow = Some(Decimal::ONE);
+            assert!(CashFlowSqlite::save(&conn, "cash_flows", restated).is_err());
+        }
+
+        // Read back from the file
+        let conn = Connection::open(&path).unwrap();
+        assert_eq!(
+            BalanceSheetsSqlite::read(&conn, "balance_sheets", &balance_sheet.hash()).unwrap(),
+            Some(balance_sheet)
+        );
+        assert_eq!(
+            IncomeStatementsSqlite::read(&conn, "income_statements", &income_statement.hash())
+                .unwrap(),
+            Some(income_statement)
+        );
+        assert_eq!(
+            CashFlowSqlite::read(&conn, "cash_flows", "missing").unwrap(),
+            None
+        );
+        assert_eq!(
+            CashFlowSqlite::read_all(&conn, "cash_flows").unwrap().len(),
+            2
+        );
+        assert_eq!(
+            CashFlowSqlite::read_all_by_symbol(&conn, "cash_flows", "SBKP.JO").unwrap(),
+            vec![cash_flow.clone()]
+        );
+
+        // Tables created by older versions get the newer line items
+        conn.execute_batch("ALTER TABLE cash_flows DROP COLUMN cash_dividends_paid;")
+            .unwrap();
+        assert!(CashFlowSqlite::read_all(&conn, "cash_flows").is_err());
+        CashFlowSqlite::init(&conn, "cash_flows").unwrap();
+        let cash_flows =
+            CashFlowSqlite::read_all_by_symbol(&conn, "cash_flows", "SBKP.JO").unwrap();
+        assert_eq!(cash_flows[0].cash_dividends_paid, None);
+
+        // and keep the newest version of terms saved more than once
+        conn.execute_batch("DROP INDEX cash_flows_term;").unwrap();
+        let mut restated = cash_flow.clone();
+        restated.free_cash_flow = Some(Decimal::ONE);
+        CashFlowSqlite::save(&conn, "cash_flows", restated.clone()).unwrap();
+        CashFlowSqlite::init(&conn, "cash_flows").unwrap();
+        assert_eq!(
+            CashFlowSqlite::read_all_by_symbol(&conn, "cash_flows", "SBKP.JO").unwrap(),
+            vec![restated]
+        );
+
+        drop(conn);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,414 @@
+//! Behaviour shared by all financial statements
+
+use crate::error::{Error, ParseError};
+use crate::html::Term;
+use crate::provider::Provider;
+use crate::{value, Decimal, Period, Source, Spider, TermKind};
+use base64::prelude::*;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Version of the layout of every statement type, stored with every saved
+/// row. Kept in step with the migrations of the database tables.
+pub const SCHEMA_VERSION: i16 = 6;
+
+/// Unit a line item is reported in
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Unit {
+    /// Amount in the statement's reporting currency
+    Currency,
+    /// Number of shares
+    Shares,
+    /// Amount per share
+    PerShare,
+    /// Fraction such as a tax rate
+    Ratio,
+}
+
+/// How the value of a line item is normally signed
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Sign {
+    /// Positive, expenses included
+    Positive,
+    /// Negative, such as cash outflows
+    Negative,
+    /// Either, such as profits and net flows
+    Either,
+}
+
+/// Filing a statement was read from, for providers that say
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Filing {
+    pub fiscal_year: Option<i32>,
+    /// `FY` for annual reports, `Q1` to `Q4` for quarterly ones
+    pub fiscal_period: Option<String>,
+    /// Accession number, which identifies the filing on EDGAR
+    pub accession: Option<String>,
+}
+
+/// The kinds of financial statements
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum StatementType {
+    BalanceSheet,
+    CashFlow,
+    IncomeStatement,
+}
+
+/// Static description of a known line item
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldMeta {
+    /// Struct field and database column name
+    pub name: &'static str,
+    /// Row title on Yahoo Finance
+    pub title: &'static str,
+    /// Type in Yahoo's fundamentals timeseries, without the
+    /// `annual`, `quarterly` or `trailing` prefix
+    pub key: &'static str,
+    pub description: &'static str,
+    pub unit: Unit,
+    pub sign: Sign,
+    /// Whether the value is a total of other line items
+    pub subtotal: bool,
+    /// Name of the line item this one is a component of
+    pub parent: Option<&'static str>,
+}
+
+/// A balance sheet, cash flow or income statement for one symbol and
+/// term. Code written against this trait works for every statement kind.
+pub trait FinancialStatement: Spider + Sized {
+    /// Version of the statement's layout, stored with every saved row
+    const SCHEMA_VERSION: i16;
+
+    /// Every known line item, in declaration order
+    const FIELDS: &'static [FieldMeta];
+
+    /// Which statement this is, for providers that lay them out differently
+    const TYPE: StatementType;
+
+    /// Empty statement, to be filled in by a provider
+    fn new(symbol: &str, term: NaiveDate, period: Period, kind: TermKind, source: Source) -> Self;
+
+    /// Download the `period` statements for `symbol` from `provider`
+    fn download(provider: &dyn Provider, symbol: &str, period: Period) -> Result<Vec<Self>, Error>;
+
+    fn symbol(&self) -> &str;
+
+    /// Last day of the period covered by the statement
+    fn term(&self) -> NaiveDate;
+
+    fn period(&self) -> Period;
+
+    /// Whether the statement covers the term or the twelve months up to it
+    fn kind(&self) -> TermKind;
+
+    /// Provider the statement was downloaded from
+    fn source(&self) -> &Source;
+
+    /// Day the statement was filed, or downloaded when the provider
+    /// does not say
+    fn filed(&self) -> NaiveDate;
+
+    fn filed_mut(&mut self) -> &mut NaiveDate;
+
+    /// Filing the statement was read from, when the provider says
+    fn filing(&self) -> Option<&Filing>;
+
+    fn filing_mut(&mut self) -> &mut Option<Filing>;
+
+    /// Schema version the statement was created with
+    fn version(&self) -> i16;
+
+    fn version_mut(&mut self) -> &mut i16;
+
+    /// Known line items by field name, in declaration order
+    fn fields(&self) -> Vec<(&'static str, Option<Decimal>)>;
+
+    /// Mutable value of the field called `name`
+    fn field_mut(&mut self, name: &str) -> Option<&mut Option<Decimal>>;
+
+    /// Rows with titles that are not known to this version of the crate.
+    /// Sub-rows are keyed by their path, as in `Total Assets > Brand New Row`.
+    fn extra(&self) -> &BTreeMap<String, Option<Decimal>>;
+
+    fn extra_mut(&mut self) -> &mut BTreeMap<String, Option<Decimal>>;
+
+    /// Set the line item called `name`, either a field name or the title
+    /// of an unknown row
+    fn insert(&mut self, name: &str, value: Option<Decimal>) {
+        match self.field_mut(name) {
+            Some(field) => *field = value,
+            None => {
+                self.extra_mut().insert(name.to_string(), value);
+            }
+        }
+    }
+
+    /// Value of the line item called `name`, either a field name or the
+    /// title of an unknown row
+    fn field(&self, name: &str) -> Option<Decimal> {
+        match self.fields().into_iter().find(|(field, _)| *field == name) {
+            Some((_, value)) => value,
+            None => self.extra().get(name).copied().flatten(),
+        }
+    }
+
+    /// Metadata of the field called `name`
+    fn field_meta(name: &str) -> Option<&'static FieldMeta> {
+        Self::FIELDS.iter().find(|field| field.name == name)
+    }
+
+    /// Metadata of the field stored under a Yahoo row `title`
+    fn field_by_title(title: &str) -> Option<&'static FieldMeta> {
+        Self::FIELDS.iter().find(|field| field.title == title)
+    }
+
+    /// Fields that are components of the field called `name`
+    fn children(name: &str) -> Vec<&'static FieldMeta> {
+        Self::FIELDS
+            .iter()
+            .filter(|field| field.parent == Some(name))
+            .collect()
+    }
+
+    /// Identifies the statement's contents, used to skip duplicates. Every
+    /// part is hashed as a `name=value` line, names quoted and missing
+    /// values as `None`, so values cannot run together or swap fields.
+    fn hash(&self) -> String {
+        let mut hasher = blake3::Hasher::new();
+        let mut pair = |name: &str, value: &dyn fmt::Display| {
+            hasher.update(format!("{name:?}={value}\n").as_bytes());
+        };
+
+        pair("symbol", &self.symbol());
+        pair("term", &self.term());
+        pair("period", &self.period().as_str());
+        pair("kind", &self.kind().as_str());
+        pair("source", &self.source().as_str());
+
+        let value = |value: Option<Decimal>| match value {
+            Some(value) => value.normalize().to_string(),
+            None => "None".to_string(),
+        };
+        for (name, field) in self.fields() {
+            pair(name, &value(field));
+        }
+        for (title, field) in self.extra() {
+            pair(title, &value(*field));
+        }
+
+        let hash = hasher.finalize();
+        BASE64_STANDARD.encode(hash.as_bytes())
+    }
+
+    /// Titles of all the rows not known to this version of the crate
+    fn unknown_titles(statements: &[Self]) -> BTreeSet<String> {
+        statements
+            .iter()
+            .flat_map(|statement| statement.extra().keys().cloned())
+            .collect()
+    }
+}
+
+/// Columns of the database tables of `S`, in table order and without
+/// the row id
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+pub(crate) fn columns<S: FinancialStatement>() -> Vec<&'static str> {
+    ["symbol", "term", "period", "kind", "source"]
+        .into_iter()
+        .chain(S::FIELDS.iter().map(|field| field.name))
+        .chain(["extra", "filed", "filing", "hash", "version"])
+        .collect()
+}
+
+/// Statement of a column with the given Yahoo row titles
+pub(crate) fn from_column<S: FinancialStatement>(
+    titles: &[String],
+    values: Vec<String>,
+    term: &Term,
+    symbol: &str,
+    period: Period,
+    source: Source,
+) -> Result<S, ParseError> {
+    let mut statement = S::new(symbol, term.date, period, term.kind, source);
+
+    for (title, text) in titles.iter().zip(values.iter()) {
+        let value = value::parse(text).map_err(|_| ParseError::Value {
+            title: title.to_string(),
+            term: term.text.clone(),
+            text: text.to_string(),
+        })?;
+        let name = S::field_by_title(title).map_or(title.as_str(), |field| field.name);
+
+        statement.insert(name, value);
+    }
+
+    Ok(statement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance_sheets::BalanceSheet;
+    use crate::cash_flows::CashFlow;
+    use crate::income_statements::IncomeStatement;
+    use crate::SpiderClient;
+    use crate::{edgar, investing, yahoo};
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/yahoo");
+
+    fn fetch<S: FinancialStatement>(symbol: &str) -> Vec<S> {
+        let client = SpiderClient::builder().replay(FIXTURES).build().unwrap();
+        let html = client.fetch::<S>(symbol, Period::Annual).unwrap();
+        yahoo::parse(&html, symbol, Period::Annual).unwrap()
+    }
+
+    #[test]
+    fn test_financial_statement() {
+        let balance_sheets = fetch::<BalanceSheet>("SBKP.JO");
+        let cash_flows = fetch::<CashFlow>("SBKP.JO");
+        let income_statements = fetch::<IncomeStatement>("SBKP.JO");
+
+        let balance_sheet = &balance_sheets[0];
+        assert_eq!(balance_sheet.symbol(), "SBKP.JO");
+        assert_eq!(balance_sheet.term(), balance_sheet.term);
+        assert_eq!(balance_sheet.version(), BalanceSheet::SCHEMA_VERSION);
+        assert_eq!(balance_sheet.fields()[0].0, "total_assets");
+        assert_eq!(
+            balance_sheet.field("total_assets"),
+            balance_sheet.total_assets
+        );
+        assert_eq!(balance_sheet.field("net_debt"), None);
+        assert_eq!(balance_sheet.field("not a field"), None);
+
+        assert_eq!(
+            cash_flows[0].field("free_cash_flow"),
+            cash_flows[0].free_cash_flow
+        );
+        assert_eq!(
+            income_statements[0].field("total_revenue"),
+            income_statements[0].total_revenue
+        );
+
+        // Values moved to another field or split differently hash apart
+        let mut moved = cash_flows[1].clone();
+        (moved.operating_cash_flow, moved.investing_cash_flow) =
+            (moved.investing_cash_flow, moved.operating_cash_flow);
+        assert_ne!(moved.hash(), cash_flows[1].hash());
+        let mut split = CashFlow::new(
+            "SBKP.JO",
+            cash_flows[1].term,
+            Period::Annual,
+            TermKind::Reported,
+            Source::Yahoo,
+        );
+        (split.operating_cash_flow, split.investing_cash_flow) =
+            (Some(Decimal::new(12, 0)), Some(Decimal::new(3, 0)));
+        let mut other = split.clone();
+        (other.operating_cash_flow, other.investing_cash_flow) =
+            (Some(Decimal::ONE), Some(Decimal::new(23, 0)));
+        assert_ne!(split.hash(), other.hash());
+
+        let mut changed = balance_sheet.clone();
+        assert_eq!(changed.hash(), balance_sheet.hash());
+        changed.extra.insert("Brand New Row".to_string(), None);
+        assert_ne!(changed.hash(), balance_sheet.hash());
+        assert_eq!(changed.field("Brand New Row"), None);
+        assert!(BalanceSheet::unknown_titles(&[changed]).contains("Brand New Row"));
+    }
+
+    #[test]
+    fn test_period_columns() {
+        let client = SpiderClient::builder().replay(FIXTURES).build().unwrap();
+        let parse = |html: String, period| BalanceSheet::parse(&html, "SBKP.JO", period).unwrap();
+
+        // Five annual columns
+        let annual = client
+            .fetch::<BalanceSheet>("SBKP.JO", Period::Annual)
+            .unwrap();
+        assert_eq!(parse(annual, Period::Annual).len(), 5);
+
+        // Nine quarterly columns
+        let quarterly = client
+            .fetch::<BalanceSheet>("SBKP.JO", Period::Quarterly)
+            .unwrap();
+        let balance_sheets = parse(quarterly, Period::Quarterly);
+        assert_eq!(balance_sheets.len(), 9);
+        assert_eq!(
+            balance_sheets[8].term,
+            NaiveDate::from_ymd_opt(2021, 12, 31).unwrap()
+        );
+        assert_eq!(
+            balance_sheets[8].total_assets,
+            Some(Decimal::new(232996620000, 2))
+        );
+
+        // TTM and three quarterly columns, the TTM ending with the latest quarter
+        let html = client
+            .fetch::<CashFlow>("SBKP.JO", Period::Quarterly)
+            .unwrap();
+        let cash_flows = CashFlow::parse(&html, "SBKP.JO", Period::Quarterly).unwrap();
+        assert_eq!(cash_flows.len(), 4);
+        assert_eq!(cash_flows[0].kind, TermKind::Ttm);
+        assert_eq!(cash_flows[0].term, cash_flows[1].term);
+        assert_eq!(
+            cash_flows[3].term,
+            NaiveDate::from_ymd_opt(2023, 6, 30).unwrap()
+        );
+    }
+
+    fn check_fields<S: FinancialStatement + Default>() {
+        let mut statement = S::default();
+        let names: Vec<&str> = statement.fields().iter().map(|(name, _)| *name).collect();
+
+        assert_eq!(
+            names,
+            S::FIELDS.iter().map(|field| field.name).collect::<Vec<_>>()
+        );
+
+        for field in S::FIELDS {
+            assert_eq!(S::field_by_title(field.title), Some(field));
+            assert!(field
+                .parent
+                .is_none_or(|parent| S::field_meta(parent).is_some()));
+
+            *statement.field_mut(field.name).unwrap() = Some(Decimal::ONE);
+            assert_eq!(statement.field(field.name), Some(Decimal::ONE));
+        }
+
+        for (_, name) in investing::titles(S::TYPE)
+            .iter()
+            .chain(edgar::tags(S::TYPE))
+        {
+            assert!(S::field_meta(name).is_some());
+        }
+    }
+
+    #[test]
+    fn test_field_metadata() {
+        check_fields::<BalanceSheet>();
+        check_fields::<CashFlow>();
+        check_fields::<IncomeStatement>();
+
+        let operating_income = IncomeStatement::field_by_title("Operating Income").unwrap();
+        assert_eq!(operating_income.name, "operating_income");
+        assert!(operating_income.subtotal);
+        assert_eq!(
+            CashFlow::field_meta("capital_expenditure").unwrap().sign,
+            Sign::Negative
+        );
+        assert_eq!(
+            IncomeStatement::children("net_interest_income")
+                .iter()
+                .map(|field| field.name)
+                .collect::<Vec<_>>(),
+            ["interest_income", "interest_expense"]
+        );
+    }
+}
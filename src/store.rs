@@ -0,0 +1,546 @@
+//! Storage of downloaded statements
+//!
+//! A [`StatementStore`] keeps the statements of one type, whatever the
+//! database behind it. Code written against it can move from PostgreSQL
+//! to SQLite, or use a [`MemoryStore`] in tests, without changes to the
+//! fetching and parsing.
+
+use crate::error::Error;
+use crate::FinancialStatement;
+use chrono::{DateTime, NaiveDate, Utc};
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use std::marker::PhantomData;
+
+/// What to do when saving a statement that is already stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Keep the stored statement as it is
+    Skip,
+    /// Overwrite the stored statement
+    Update,
+}
+
+/// Outcome of an upsert
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Saved {
+    /// The statement was not stored yet
+    Inserted,
+    /// The statement was stored already, as it is or skipped
+    Unchanged,
+    /// The stored statement was overwritten
+    Updated,
+    /// Other versions of the statement's term were moved to the history
+    Restated,
+}
+
+/// Saves and reads statements of one type
+pub trait StatementStore {
+    type Statement: FinancialStatement;
+
+    /// Create the storage if it does not exist yet
+    fn init(&mut self) -> Result<(), Error>;
+
+    /// Save a statement, failing if a version of its term is stored.
+    /// Restatements go through [`revise`](Self::revise).
+    fn save(&mut self, statement: &Self::Statement) -> Result<(), Error>;
+
+    /// Save all the statements, stopping at the first failure
+    fn save_many(&mut self, statements: &[Self::Statement]) -> Result<(), Error> {
+        statements
+            .iter()
+            .try_for_each(|statement| self.save(statement))
+    }
+
+    /// Save a statement, doing `on_conflict` when one with the same hash
+    /// is stored. Statements with the same hash have the same values, so
+    /// only the filing and version can change. Safe to repeat. Fails if
+    /// another version of the term is stored, like [`save`](Self::save).
+    fn upsert(
+        &mut self,
+        statement: &Self::Statement,
+        on_conflict: OnConflict,
+    ) -> Result<Saved, Error>;
+
+    /// Statement identified by `hash`
+    fn read(&mut self, hash: &str) -> Result<Option<Self::Statement>, Error>;
+
+    /// Statements of `symbol` with a term from `from` to `to`, oldest first
+    fn read_range(
+        &mut self,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Self::Statement>, Error>;
+
+    /// Save a statement as the current version of its term from `at`.
+    /// Versions stored for the same symbol, term, period, kind and source
+    /// are restated by it, and kept as history valid until `at`.
+    fn revise(&mut self, statement: &Self::Statement, at: DateTime<Utc>) -> Result<Saved, Error>;
+
+    /// Statements of `symbol` with a term from `from` to `to` as they
+    /// were stored at `at`, oldest first, for point in time backtests
+    fn read_as_of(
+        &mut self,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<Self::Statement>, Error>;
+
+    /// Delete the statement identified by `hash`, returning whether it
+    /// was stored. Its history is kept.
+    fn delete(&mut self, hash: &str) -> Result<bool, Error>;
+}
+
+/// Statements kept in memory, mostly for tests
+#[derive(Debug, Clone)]
+pub struct MemoryStore<S> {
+    versions: Vec<Version<S>>,
+}
+
+/// Statement and the time it was current, up to now when `valid_to` is
+/// not set
+#[derive(Debug, Clone)]
+struct Version<S> {
+    statement: S,
+    valid_from: DateTime<Utc>,
+    valid_to: Option<DateTime<Utc>>,
+}
+
+impl<S> MemoryStore<S> {
+    pub fn new() -> Self {
+        MemoryStore { versions: vec![] }
+    }
+}
+
+impl<S> Default for MemoryStore<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: FinancialStatement + Clone> MemoryStore<S> {
+    fn current(&self) -> impl Iterator<Item = &S> {
+        self.versions
+            .iter()
+            .filter(|version| version.valid_to.is_none())
+            .map(|version| &version.statement)
+    }
+
+    fn push(&mut self, statement: &S, valid_from: DateTime<Utc>) {
+        self.versions.push(Version {
+            statement: statement.clone(),
+            valid_from,
+            valid_to: None,
+        });
+    }
+}
+
+/// Whether `a` and `b` are versions of the same term
+fn same_term<S: FinancialStatement>(a: &S, b: &S) -> bool {
+    (a.symbol(), a.term(), a.period(), a.kind(), a.source())
+        == (b.symbol(), b.term(), b.period(), b.kind(), b.source())
+}
+
+impl<S: FinancialStatement + Clone> StatementStore for MemoryStore<S> {
+    type Statement = S;
+
+    fn init(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Fails with [`Error::SQL`] on other versions of the term, like the
+    /// databases' unique term key
+    fn save(&mut self, statement: &S) -> Result<(), Error> {
+        if self.current().any(|stored| same_term(stored, statement)) {
+            return Err(Error::SQL);
+        }
+
+        self.push(statement, Utc::now());
+        Ok(())
+    }
+
+    fn upsert(&mut self, statement: &S, on_conflict: OnConflict) -> Result<Saved, Error> {
+        let hash = statement.hash();
+        let Some(stored) = self
+            .versions
+            .iter_mut()
+            .filter(|version| version.valid_to.is_none())
+            .map(|version| &mut version.statement)
+            .find(|stored| stored.hash() == hash)
+        else {
+            self.save(statement)?;
+            return Ok(Saved::Inserted);
+        };
+
+        if on_conflict == OnConflict::Skip
+            || (stored.filed(), stored.filing(), stored.version())
+                == (statement.filed(), statement.filing(), statement.version())
+        {
+            return Ok(Saved::Unchanged);
+        }
+
+        *stored = statement.clone();
+        Ok(Saved::Updated)
+    }
+
+    fn revise(&mut self, statement: &S, at: DateTime<Utc>) -> Result<Saved, Error> {
+        let hash = statement.hash();
+        if self.current().any(|stored| stored.hash() == hash) {
+            return Ok(Saved::Unchanged);
+        }
+
+        let mut replaced = false;
+        for version in &mut self.versions {
+            let stored = &version.statement;
+            if version.valid_to.is_none() && same_term(stored, statement) {
+                version.valid_to = Some(at);
+                replaced = true;
+            }
+        }
+
+        self.push(statement, at);
+        Ok(if replaced {
+            Saved::Restated
+        } else {
+            Saved::Inserted
+        })
+    }
+
+    fn read(&mut self, hash: &str) -> Result<Option<S>, Error> {
+        Ok(self
+            .current()
+            .find(|statement| statement.hash() == hash)
+            .cloned())
+    }
+
+    fn read_range(
+        &mut self,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<S>, Error> {
+        let mut statements: Vec<S> = self
+            .current()
+            .filter(|statement| {
+                statement.symbol() == symbol && (from..=to).contains(&statement.term())
+            })
+            .cloned()
+            .collect();
+        statements.sort_by_key(|statement| statement.term());
+
+        Ok(statements)
+    }
+
+    fn read_as_of(
+        &mut self,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<S>, Error> {
+        let mut statements: Vec<S> = self
+            .versions
+            .iter()
+            .filter(|version| {
+                version.valid_from <= at && version.valid_to.is_none_or(|valid_to| valid_to > at)
+            })
+            .map(|version| &version.statement)
+            .filter(|statement| {
+                statement.symbol() == symbol && (from..=to).contains(&statement.term())
+            })
+            .cloned()
+            .collect();
+        statements.sort_by_key(|statement| statement.term());
+
+        Ok(statements)
+    }
+
+    fn delete(&mut self, hash: &str) -> Result<bool, Error> {
+        let stored = self.versions.len();
+        self.versions
+            .retain(|version| version.valid_to.is_some() || version.statement.hash() != hash);
+
+        Ok(self.versions.len() < stored)
+    }
+}
+
+/// Statements of one type in a PostgreSQL table
+#[cfg(feature = "postgres")]
+pub struct PostgresStore<S> {
+    client: postgres::Client,
+    table: String,
+    owner: String,
+    statement: PhantomData<S>,
+}
+
+#[cfg(feature = "postgres")]
+impl<S> PostgresStore<S> {
+    /// Store in `table`, created with `owner` as its owner
+    pub fn new(client: postgres::Client, table: &str, owner: &str) -> Self {
+        PostgresStore {
+            client,
+            table: table.to_string(),
+            owner: owner.to_string(),
+            statement: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<S: FinancialStatement> StatementStore for PostgresStore<S> {
+    type Statement = S;
+
+    fn init(&mut self) -> Result<(), Error> {
+        crate::database::init::<S>(&mut self.client, &self.table, &self.owner)
+    }
+
+    fn save(&mut self, statement: &S) -> Result<(), Error> {
+        crate::database::save(&mut self.client, &self.table, statement)
+    }
+
+    fn upsert(&mut self, statement: &S, on_conflict: OnConflict) -> Result<Saved, Error> {
+        crate::database::upsert(&mut self.client, &self.table, statement, on_conflict)
+    }
+
+    fn read(&mut self, hash: &str) -> Result<Option<S>, Error> {
+        crate::database::read(&mut self.client, &self.table, hash)
+    }
+
+    fn read_range(
+        &mut self,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<S>, Error> {
+        crate::database::read_range(&mut self.client, &self.table, symbol, from, to)
+    }
+
+    fn revise(&mut self, statement: &S, at: DateTime<Utc>) -> Result<Saved, Error> {
+        crate::database::revise(&mut self.client, &self.table, statement, at)
+    }
+
+    fn read_as_of(
+        &mut self,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<S>, Error> {
+        crate::database::read_as_of(&mut self.client, &self.table, symbol, from, to, at)
+    }
+
+    fn delete(&mut self, hash: &str) -> Result<bool, Error> {
+        crate::database::delete(&mut self.client, &self.table, hash)
+    }
+}
+
+/// Statements of one type in a SQLite table
+#[cfg(feature = "sqlite")]
+pub struct SqliteStore<S> {
+    conn: rusqlite::Connection,
+    table: String,
+    statement: PhantomData<S>,
+}
+
+#[cfg(feature = "sqlite")]
+impl<S> SqliteStore<S> {
+    /// Store in `table`
+    pub fn new(conn: rusqlite::Connection, table: &str) -> Self {
+        SqliteStore {
+            conn,
+            table: table.to_string(),
+            statement: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<S: FinancialStatement> StatementStore for SqliteStore<S> {
+    type Statement = S;
+
+    fn init(&mut self) -> Result<(), Error> {
+        crate::sqlite::init::<S>(&self.conn, &self.table)
+    }
+
+    fn save(&mut self, statement: &S) -> Result<(), Error> {
+        crate::sqlite::save(&self.conn, &self.table, statement)
+    }
+
+    fn upsert(&mut self, statement: &S, on_conflict: OnConflict) -> Result<Saved, Error> {
+        crate::sqlite::upsert(&self.conn, &self.table, statement, on_conflict)
+    }
+
+    fn read(&mut self, hash: &str) -> Result<Option<S>, Error> {
+        crate::sqlite::read(&self.conn, &self.table, hash)
+    }
+
+    fn read_range(
+        &mut self,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<S>, Error> {
+        crate::sqlite::read_range(&self.conn, &self.table, symbol, from, to)
+    }
+
+    fn revise(&mut self, statement: &S, at: DateTime<Utc>) -> Result<Saved, Error> {
+        crate::sqlite::revise(&self.conn, &self.table, statement, at)
+    }
+
+    fn read_as_of(
+        &mut self,
+        symbol: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<S>, Error> {
+        crate::sqlite::read_as_of(&self.conn, &self.table, symbol, from, to, at)
+    }
+
+    fn delete(&mut self, hash: &str) -> Result<bool, Error> {
+        crate::sqlite::delete(&self.conn, &self.table, hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cash_flows::CashFlow;
+    use crate::{Decimal, Period, Source, TermKind};
+    use chrono::Duration;
+
+    fn cash_flow(symbol: &str, year: i32) -> CashFlow {
+        let term = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+        let mut cash_flow = CashFlow::new(
+            symbol,
+            term,
+            Period::Annual,
+            TermKind::Reported,
+            Source::Yahoo,
+        );
+        cash_flow.operating_cash_flow = Some(Decimal::new(year.into(), 0));
+        cash_flow
+    }
+
+    /// Same checks for every backend
+    fn check(mut store: impl StatementStore<Statement = CashFlow>) {
+        let date = |year| NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+        let statements = vec![
+            cash_flow("SBKP.JO", 2023),
+            cash_flow("SBKP.JO", 2021),
+            cash_flow("SBKP.JO", 2022),
+            cash_flow("AAPL", 2022),
+        ];
+
+        store.init().unwrap();
+        store.save_many(&statements).unwrap();
+        assert!(store.save(&statements[0]).is_err());
+
+        // Upserts can be repeated
+        let mut refiled = statements[1].clone();
+        refiled.filed = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert_eq!(
+            store.upsert(&refiled, OnConflict::Skip).unwrap(),
+            Saved::Unchanged
+        );
+        assert_eq!(
+            store.upsert(&statements[1], OnConflict::Update).unwrap(),
+            Saved::Unchanged
+        );
+        assert_eq!(
+            store.upsert(&refiled, OnConflict::Update).unwrap(),
+            Saved::Updated
+        );
+        assert_eq!(store.read(&refiled.hash()).unwrap(), Some(refiled.clone()));
+        store.upsert(&statements[1], OnConflict::Update).unwrap();
+
+        let statement = cash_flow("SBKP.JO", 2020);
+        assert_eq!(
+            store.upsert(&statement, OnConflict::Skip).unwrap(),
+            Saved::Inserted
+        );
+        assert!(store.delete(&statement.hash()).unwrap());
+
+        assert_eq!(
+            store.read(&statements[0].hash()).unwrap(),
+            Some(statements[0].clone())
+        );
+        assert_eq!(store.read("missing").unwrap(), None);
+
+        let range = store.read_range("SBKP.JO", date(2021), date(2023)).unwrap();
+        assert_eq!(range, vec![statements[1].clone(), statements[2].clone()]);
+
+        // Restatements keep the replaced version as history
+        let now = Utc::now();
+        let time = |days| now + Duration::days(days);
+        let mut restated = statements[2].clone();
+        restated.operating_cash_flow = Some(Decimal::ONE);
+        assert!(store.save(&restated).is_err());
+        assert!(store.upsert(&restated, OnConflict::Update).is_err());
+        assert_eq!(
+            store.revise(&statements[2], time(1)).unwrap(),
+            Saved::Unchanged
+        );
+        assert_eq!(store.revise(&restated, time(10)).unwrap(), Saved::Restated);
+        assert_eq!(store.revise(&restated, time(11)).unwrap(), Saved::Unchanged);
+        assert_eq!(store.read(&statements[2].hash()).unwrap(), None);
+        assert_eq!(
+            store.read_range("SBKP.JO", date(2022), date(2023)).unwrap(),
+            vec![restated.clone()]
+        );
+
+        let mut as_of = |days| {
+            store
+                .read_as_of("SBKP.JO", date(2022), date(2023), time(days))
+                .unwrap()
+        };
+        assert_eq!(as_of(-1), vec![]);
+        assert_eq!(as_of(9), vec![statements[2].clone()]);
+        assert_eq!(as_of(10), vec![restated.clone()]);
+
+        assert!(store.delete(&statements[0].hash()).unwrap());
+        assert!(!store.delete(&statements[0].hash()).unwrap());
+        assert_eq!(store.read(&statements[0].hash()).unwrap(), None);
+        assert_eq!(
+            store.read_range("SBKP.JO", date(2000), date(2100)).unwrap(),
+            vec![statements[1].clone(), restated]
+        );
+    }
+
+    #[test]
+    fn test_stores() {
+        check(MemoryStore::new());
+
+        #[cfg(feature = "sqlite")]
+        check(SqliteStore::new(
+            rusqlite::Connection::open_in_memory().unwrap(),
+            "cash_flows",
+        ));
+
+        #[cfg(feature = "postgres")]
+        {
+            let var = |name: &str| std::env::var(name).unwrap_or_else(|_| panic!("{name} not set"));
+            let db_url = format!(
+                "postgres://{}:{}@{}:{}/{}",
+                var("DB_USER"),
+                var("DB_PASSWORD"),
+                var("DB_HOST"),
+                var("DB_PORT"),
+                var("DB_NAME")
+            );
+            let mut client = postgres::Client::connect(&db_url, postgres::NoTls).unwrap();
+            client
+                .batch_execute(
+                    "DROP TABLE IF EXISTS cash_flows_test_store, cash_flows_test_store_history;",
+                )
+                .unwrap();
+
+            check(PostgresStore::<CashFlow>::new(
+                client,
+                "cash_flows_test_store",
+                &var("DB_USER"),
+            ));
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Statements from Yahoo's fundamentals timeseries
+//!
+//! The timeseries endpoint returns the raw values behind the statement
+//! pages as JSON. The pages embed the same JSON, so either a response
+//! body or a saved page can be parsed.
+
+use crate::error::ParseError;
+use crate::html::{self, Column, Term};
+use crate::statement::{self, FinancialStatement, Unit};
+use crate::{value, Decimal, Period, Source, TermKind};
+use chrono::NaiveDate;
+use scraper::Html;
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// Start of the requested history, as used by Yahoo's own pages
+const PERIOD1: i64 = 493590046;
+/// End of the requested history, far enough ahead to include every term
+const PERIOD2: i64 = 4102444800;
+
+/// Where statements are read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// Scrape the rendered statement page
+    #[default]
+    Html,
+    /// Read the fundamentals timeseries, falling back to the page when
+    /// it cannot be fetched or holds no statements
+    Timeseries,
+}
+
+/// Url of the timeseries of every known line item of `S`, including the
+/// trailing twelve months
+pub fn url<S: FinancialStatement>(root: &str, symbol: &str, period: Period) -> String {
+    let types: Vec<String> = [period.as_str(), "trailing"]
+        .iter()
+        .flat_map(|prefix| {
+            S::FIELDS
+                .iter()
+                .map(move |field| format!("{prefix}{}", field.key))
+        })
+        .collect();
+
+    format!(
+        "{root}/ws/fundamentals-timeseries/v1/finance/timeseries/{symbol}?symbol={symbol}&type={}&period1={PERIOD1}&period2={PERIOD2}",
+        types.join(",")
+    )
+}
+
+/// Parse all the statements in timeseries JSON, given as a response body
+/// or a page embedding it
+pub fn parse<S: FinancialStatement>(
+    json: &str,
+    symbol: &str,
+    period: Period,
+) -> Result<Vec<S>, ParseError> {
+    columns::<S>(json, period)?
+        .into_iter()
+        .map(|(term, titles, values)| {
+            statement::from_column(&titles, values, &term, symbol, period, Source::Yahoo)
+        })
+        .collect()
+}
+
+/// Group the values in `json` by term, TTM first and then newest first.
+/// Unknown types are titled like the rows of the statement pages.
+/// Amounts and share counts are converted to thousands, the unit of the
+/// statement pages. Values of unknown types are kept as reported.
+fn columns<S: FinancialStatement>(json: &str, period: Period) -> Result<Vec<Column>, ParseError> {
+    let json = extract(json)?;
+    let results = json
+        .pointer("/timeseries/result")
+        .and_then(Value::as_array)
+        .ok_or_else(|| ParseError::Json("no timeseries result".to_string()))?;
+    let mut columns: BTreeMap<_, (Vec<String>, Vec<String>)> = BTreeMap::new();
+
+    for result in results {
+        let Some(kind) = result.pointer("/meta/type/0").and_then(Value::as_str) else {
+            continue;
+        };
+        let (key, term_kind) = if let Some(key) = kind.strip_prefix(period.as_str()) {
+            (key, TermKind::Reported)
+        } else if let Some(key) = kind.strip_prefix("trailing") {
+            (key, TermKind::Ttm)
+        } else {
+            continue;
+        };
+        let (title, scale) = match S::FIELDS.iter().find(|field| field.key == key) {
+            Some(field) => (
+                field.title.to_string(),
+                matches!(field.unit, Unit::Currency | Unit::Shares),
+            ),
+            None => (title(key), false),
+        };
+
+        for entry in result
+            .get(kind)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let (Some(date), Some(raw)) = (
+                entry.get("asOfDate").and_then(Value::as_str),
+                entry.pointer("/reportedValue/raw"),
+            ) else {
+                continue;
+            };
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| ParseError::Term(date.to_string()))?;
+            let err = || ParseError::Value {
+                title: title.clone(),
+                term: date.to_string(),
+                text: raw.to_string(),
+            };
+            let mut value = value::parse(&raw.to_string())
+                .map_err(|_| err())?
+                .ok_or_else(err)?;
+
+            if scale {
+                value /= Decimal::ONE_THOUSAND;
+            }
+
+            // The TTM column sorts ahead of the reported terms
+            let (titles, values) = columns
+                .entry((term_kind == TermKind::Reported, Reverse(date)))
+                .or_default();
+            titles.push(title.clone());
+            values.push(value.normalize().to_string());
+        }
+    }
+
+    Ok(columns
+        .into_iter()
+        .map(|((reported, Reverse(date)), (titles, values))| {
+            let (text, kind) = if reported {
+                (date.format("%-m/%-d/%Y").to_string(), TermKind::Reported)
+            } else {
+                ("TTM".to_string(), TermKind::Ttm)
+            };
+
+            (Term { text, date, kind }, titles, values)
+        })
+        .collect())
+}
+
+/// Row title of the timeseries type `key`, as in `Cash Flow from
+/// Discontinued Operation` for `CashFlowFromDiscontinuedOperation`
+fn title(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        // Words start at a capital after a lower case letter or digit, or
+        // at the last capital of an acronym followed by a lower case letter
+        let starts = i > 0
+            && c.is_uppercase()
+            && (!chars[i - 1].is_uppercase()
+                || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+
+        match words.last_mut() {
+            Some(word) if !starts => word.push(c),
+            _ => words.push(c.to_string()),
+        }
+    }
+
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| match word.as_str() {
+            "After" | "And" | "As" | "At" | "By" | "For" | "From" | "In" | "Of" | "On" | "Or"
+            | "The" | "To"
+                if i > 0 =>
+            {
+                word.to_lowercase()
+            }
+            _ => word.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Day the trailing twelve months in `text` end, a timeseries response
+/// or a page embedding one
+pub(crate) fn trailing_as_of(text: &str) -> Option<NaiveDate> {
+    extract(text)
+        .ok()?
+        .pointer("/timeseries/result")?
+        .as_array()?
+        .iter()
+        .filter_map(|result| {
+            let kind = result.pointer("/meta/type/0")?.as_str()?;
+            kind.starts_with("trailing")
+                .then(|| result.get(kind)?.as_array())?
+        })
+        .flatten()
+        .filter_map(|entry| {
+            let date = entry.get("asOfDate")?.as_str()?;
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+        })
+        .max()
+}
+
+/// The timeseries JSON in `text`, either a response body or a page
+/// embedding it
+fn extract(text: &str) -> Result<Value, ParseError> {
+    let json = |text: &str| {
+        serde_json::from_str::<Value>(text).map_err(|err| ParseError::Json(err.to_string()))
+    };
+
+    if text.trim_start().starts_with('{') {
+        return json(text);
+    }
+
+    let document = Html::parse_document(text);
+    let scripts = html::selector(r#"script[type="application/json"]"#)?;
+    let script = document
+        .select(&scripts)
+        .find(|script| {
+            script
+                .value()
+                .attr("data-url")
+                .is_some_and(|url| url.contains("fundamentals-timeseries"))
+        })
+        .ok_or_else(|| ParseError::Json("no timeseries embedded in the page".to_string()))?;
+    let embedded = json(&script.text().collect::<String>())?;
+
+    // Pages wrap the response, with the body as a string
+    match embedded.get("body").and_then(Value::as_str) {
+        Some(body) => json(body),
+        None => Ok(embedded),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cash_flows::CashFlow;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::rate_limit::RateLimiter;
+    use crate::retry::RetryPolicy;
+    use crate::yahoo::YahooProvider;
+    use crate::{Decimal, Provider, SpiderClient};
+    use std::fs;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+    #[test]
+    fn test_timeseries() {
+        let symbol = "SBKP.JO";
+        let json = fs::read_to_string(format!(
+            "{FIXTURES}/yahoo-timeseries/SBKP.JO_cash-flow.json"
+        ))
+        .unwrap();
+
+        let cash_flows = parse::<CashFlow>(&json, symbol, Period::Annual).unwrap();
+        assert_eq!(cash_flows.len(), 5);
+        assert_eq!(cash_flows[0].kind, TermKind::Ttm);
+        assert_eq!(
+            cash_flows[0].term,
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()
+        );
+        assert_eq!(
+            cash_flows[0].operating_cash_flow,
+            Some(Decimal::new(50645920, 0))
+        );
+        assert_eq!(
+            cash_flows[1].term,
+            NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+        );
+        assert_eq!(
+            cash_flows[1].capital_expenditure,
+            Some(Decimal::new(-4386000, 0))
+        );
+        assert_eq!(
+            cash_flows[1]
+                .extra
+                .get("Cash Flow from Discontinued Operation"),
+            Some(&Some(Decimal::new(125000000, 0)))
+        );
+        assert_eq!(
+            cash_flows[4].operating_cash_flow,
+            Some(Decimal::new(41394000, 0))
+        );
+        assert_eq!(cash_flows[4].capital_expenditure, None);
+        assert_eq!(
+            title("InterestIncomeAfterProvisionForLoanLoss"),
+            "Interest Income after Provision for Loan Loss"
+        );
+        assert_eq!(title("NetPPEPurchaseAndSale"), "Net PPE Purchase and Sale");
+
+        // Embedded in a page
+        let wrapped = serde_json::json!({ "status": 200, "body": json }).to_string();
+        let page = format!(
+            r#"<html><body><script type="application/json" data-sveltekit-fetched data-url="https://query2.finance.yahoo.com/ws/fundamentals-timeseries/v1/finance/timeseries/SBKP.JO?symbol=SBKP.JO">{wrapped}</script></body></html>"#
+        );
+        assert_eq!(
+            parse::<CashFlow>(&page, symbol, Period::Annual).unwrap(),
+            cash_flows
+        );
+        assert!(matches!(
+            parse::<CashFlow>("<html></html>", symbol, Period::Annual),
+            Err(ParseError::Json(_))
+        ));
+
+        // Falls back to the page when there is no timeseries
+        let html =
+            fs::read_to_string(format!("{FIXTURES}/yahoo/quote_SBKP.JO_cash-flow.html")).unwrap();
+        let server = MockServer::start(vec![
+            MockResponse::status(404, ""),
+            MockResponse::ok(&html),
+            MockResponse::ok(&json),
+        ]);
+        let client = SpiderClient::builder()
+            .base_url(&server.url)
+            .retry(RetryPolicy::none())
+            .rate_limit(RateLimiter::unlimited())
+            .build()
+            .unwrap();
+        let provider = YahooProvider::new(client)
+            .backend(Backend::Timeseries)
+            .timeseries_url(&server.url);
+
+        let cash_flows = provider.cash_flows(symbol, Period::Annual).unwrap();
+        assert_eq!(cash_flows.len(), 6);
+
+        let cash_flows = provider.cash_flows(symbol, Period::Annual).unwrap();
+        assert_eq!(cash_flows.len(), 5);
+
+        let requests = server.requests();
+        assert!(requests[0][0].starts_with(
+            "GET /ws/fundamentals-timeseries/v1/finance/timeseries/SBKP.JO?symbol=SBKP.JO&type=annualCashFlowsfromusedinOperatingActivitiesDirect,"
+        ));
+        assert_eq!(requests[1][0], "GET /quote/SBKP.JO/cash-flow HTTP/1.1");
+    }
+}
@@ -0,0 +1,48 @@
+//! Numeric values of financial statement line items
+
+pub use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Parse a formatted value such as `"-2,717,000.00"` or `"3.2e11"`.
+/// Missing values (`"--"` or blank) are `None`.
+pub fn parse(text: &str) -> Result<Option<Decimal>, rust_decimal::Error> {
+    let text = text.trim();
+
+    if text.is_empty() || text == "--" {
+        return Ok(None);
+    }
+
+    let text = text.replace(',', "");
+
+    Decimal::from_str(&text)
+        .or_else(|err| {
+            if text.contains(['e', 'E']) {
+                Decimal::from_scientific(&text)
+            } else {
+                Err(err)
+            }
+        })
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(
+            parse("-2,717,000.00"),
+            Ok(Some(Decimal::new(-271700000, 2)))
+        );
+        assert_eq!(
+            parse(" 1,675,775.23 "),
+            Ok(Some(Decimal::new(167577523, 2)))
+        );
+        assert_eq!(parse("0.00"), Ok(Some(Decimal::new(0, 2))));
+        assert_eq!(parse("3.2e3"), Ok(Some(Decimal::new(3200, 0))));
+        assert_eq!(parse("--"), Ok(None));
+        assert_eq!(parse(""), Ok(None));
+        assert!(parse("n/a").is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! Statements from Yahoo Finance
+//!
+//! Yahoo shows every statement on a page of its own, with one row per
+//! line item and one column per term. The values behind the pages can
+//! also be read from the fundamentals timeseries, see
+//! [`timeseries`](crate::timeseries).
+
+use crate::balance_sheets::BalanceSheet;
+use crate::cash_flows::CashFlow;
+use crate::error::{Error, ParseError};
+use crate::html;
+use crate::income_statements::IncomeStatement;
+use crate::provider::Provider;
+use crate::statement::{self, FinancialStatement, StatementType};
+use crate::timeseries::{self, Backend};
+use crate::{Period, Source, SpiderClient, YAHOO_TIMESERIES_ROOT};
+use scraper::Html;
+
+/// Path of the page of `statement`, below the quote of a symbol
+pub fn page(statement: StatementType) -> &'static str {
+    match statement {
+        StatementType::BalanceSheet => "balance-sheet",
+        StatementType::CashFlow => "cash-flow",
+        StatementType::IncomeStatement => "financials",
+    }
+}
+
+/// Url of the `period` page of `statement` for `symbol`
+pub fn url(root: &str, statement: StatementType, symbol: &str, period: Period) -> String {
+    format!(
+        "{root}/quote/{symbol}/{}{}",
+        page(statement),
+        period.query()
+    )
+}
+
+/// Parse all the statements on a downloaded statement page. The TTM
+/// column is dated from the timeseries embedded in the page, or else as
+/// of the latest reported column, and left out without either. Unknown
+/// sub-rows are kept in the extra rows under their path, as in
+/// `Total Assets > Current Assets > Brand New Row`.
+pub fn parse<S: FinancialStatement>(
+    html: &str,
+    symbol: &str,
+    period: Period,
+) -> Result<Vec<S>, ParseError> {
+    let document = Html::parse_document(html);
+    let terms = html::terms(&document, timeseries::trailing_as_of(html))?;
+    let rows = html::rows(&document)?;
+    let titles: Vec<String> = rows
+        .iter()
+        .map(|row| match S::field_by_title(&row.title) {
+            Some(_) => row.title.clone(),
+            None => row.path(),
+        })
+        .collect();
+
+    // One statement per column, rows missing a value leave it empty
+    terms
+        .iter()
+        .enumerate()
+        .filter_map(|(column, term)| Some((column, term.as_ref()?)))
+        .map(|(column, term)| {
+            let values = rows
+                .iter()
+                .map(|row| row.values.get(column).cloned().unwrap_or_default())
+                .collect();
+
+            statement::from_column(&titles, values, term, symbol, period, Source::Yahoo)
+        })
+        .collect()
+}
+
+/// Statements from Yahoo Finance
+#[derive(Debug, Clone)]
+pub struct YahooProvider {
+    client: SpiderClient,
+    backend: Backend,
+    timeseries_url: String,
+}
+
+impl YahooProvider {
+    pub fn new(client: SpiderClient) -> Self {
+        YahooProvider {
+            client,
+            backend: Backend::default(),
+            timeseries_url: YAHOO_TIMESERIES_ROOT.to_string(),
+        }
+    }
+
+    /// Where statements are read from, defaults to the statement pages
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Root url of the fundamentals timeseries, defaults to
+    /// [`YAHOO_TIMESERIES_ROOT`]. Pages are fetched from the client's
+    /// [`base_url`](SpiderClient::base_url).
+    pub fn timeseries_url(mut self, timeseries_url: &str) -> Self {
+        self.timeseries_url = timeseries_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Download and parse the `period` statements of `S` for `symbol`
+    pub fn fetch<S: FinancialStatement>(
+        &self,
+        symbol: &str,
+        period: Period,
+    ) -> Result<Vec<S>, Error> {
+        if self.backend == Backend::Timeseries {
+            let url = timeseries::url::<S>(&self.timeseries_url, symbol, period);
+            println!(
+                "---> Fetching {} timeseries ({period}) for: {symbol}",
+                S::NAME
+            );
+
+            match self
+                .client
+                .get(&url)
+                .map(|json| timeseries::parse::<S>(&json, symbol, period))
+            {
+                Ok(Ok(statements)) if !statements.is_empty() => return Ok(statements),
+                _ => println!("---> No timeseries, falling back to the page"),
+            }
+        }
+
+        let html = self.client.fetch::<S>(symbol, period)?;
+        Ok(parse(&html, symbol, period)?)
+    }
+}
+
+impl Provider for YahooProvider {
+    fn source(&self) -> Source {
+        Source::Yahoo
+    }
+
+    fn balance_sheets(&self, symbol: &str, period: Period) -> Result<Vec<BalanceSheet>, Error> {
+        self.fetch(symbol, period)
+    }
+
+    fn cash_flows(&self, symbol: &str, period: Period) -> Result<Vec<CashFlow>, Error> {
+        self.fetch(symbol, period)
+    }
+
+    fn income_statements(
+        &self,
+        symbol: &str,
+        period: Period,
+    ) -> Result<Vec<IncomeStatement>, Error> {
+        self.fetch(symbol, period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TermKind, YAHOO_ROOT};
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/yahoo");
+
+    #[test]
+    fn test_yahoo() {
+        assert_eq!(
+            url(
+                YAHOO_ROOT,
+                StatementType::IncomeStatement,
+                "SBKP.JO",
+                Period::Quarterly
+            ),
+            "https://finance.yahoo.com/quote/SBKP.JO/financials?frequency=quarterly"
+        );
+
+        let client = SpiderClient::builder().replay(FIXTURES).build().unwrap();
+        let provider = YahooProvider::new(client);
+        let income_statements = provider
+            .income_statements("SBKP.JO", Period::Annual)
+            .unwrap();
+
+        assert_eq!(income_statements[0].kind, TermKind::Ttm);
+        assert!(income_statements
+            .iter()
+            .all(|income_statement| income_statement.source == Source::Yahoo));
+    }
+}